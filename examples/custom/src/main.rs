@@ -3,6 +3,7 @@
 //!    example, we process requests in a separate "server" thread using MPSC
 //!    channels.
 //! 2. Custom RPC methods defined with the macro.
+//! 3. Serving custom RPC methods with a typed `server::Router`.
 
 use ethrpc::{jsonrpc, types::Empty};
 use std::time::SystemTime;
@@ -66,7 +67,7 @@ fn main() {
 
 mod server {
     use super::custom;
-    use ethrpc::jsonrpc;
+    use ethrpc::{jsonrpc, server::Router};
     use std::{error::Error, sync::mpsc, thread, time::SystemTime};
 
     pub fn start() -> Server {
@@ -109,63 +110,20 @@ mod server {
         }
     }
 
-    macro_rules! router {
-        ($req:expr; $($t:expr => |$r:ident| $f:block)*) => {{
-            let req: jsonrpc::Request = $req;
-            match req.method.as_str() {
-                $(m if m == ethrpc::method::Method::name(&$t) => {
-                    let id = req.id;
-                    let $r = param_of($t, req.params);
-                    let result = { $f };
-                    jsonrpc::Response {
-                        jsonrpc: jsonrpc::Version::V2,
-                        id: Some(id),
-                        result: result.map(|value| result_of($t, value)),
-                    }
-                })*
-                method => jsonrpc::Response {
-                    jsonrpc: jsonrpc::Version::V2,
-                    id: Some(req.id),
-                    result: Err(jsonrpc::Error {
-                        code: jsonrpc::ErrorCode::MethodNotFound,
-                        message: format!("unknown method '{method}'"),
-                        data: jsonrpc::Value::default(),
-                    })
-                }
-            }
-        }}
-    }
-
     fn handler(
         requests: mpsc::Receiver<jsonrpc::Request>,
         responses: mpsc::Sender<jsonrpc::Response>,
     ) {
-        while let Ok(request) = requests.recv() {
-            let _ = responses.send(router! {
-                request;
-                custom::Time => |_params| {
-                    Ok(SystemTime::now())
-                }
-                custom::Add => |params| {
-                    let (a, b) = params;
-                    a.checked_add(b)
-                        .ok_or_else(|| jsonrpc::Error::custom("overflow"))
-                }
+        let mut router = Router::new();
+        router
+            .register(custom::Time, |_| Ok(SystemTime::now()))
+            .register(custom::Add, |(a, b)| {
+                a.checked_add(b)
+                    .ok_or_else(|| jsonrpc::Error::custom("overflow"))
             });
-        }
-    }
 
-    fn param_of<M>(_: M, value: jsonrpc::Value) -> M::Params
-    where
-        M: ethrpc::method::Method,
-    {
-        value.params::<M>().unwrap()
-    }
-
-    fn result_of<M>(_: M, result: M::Result) -> jsonrpc::Value
-    where
-        M: ethrpc::method::Method,
-    {
-        jsonrpc::Value::for_result::<M>(result).unwrap()
+        while let Ok(request) = requests.recv() {
+            let _ = responses.send(router.handle(request));
+        }
     }
 }
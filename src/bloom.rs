@@ -3,7 +3,7 @@
 //! TODO(nlordell): This should live in its own crate and implement an actual
 //! bloom filter.
 
-use crate::{debug, serialization};
+use crate::{hex, serialization};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::{self, Debug, Formatter};
 
@@ -26,7 +26,7 @@ impl Default for Bloom {
 
 impl Debug for Bloom {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.debug_tuple("Bloom").field(&debug::Hex(&self.0)).finish()
+        f.debug_tuple("Bloom").field(&hex::Hex(&self.0)).finish()
     }
 }
 
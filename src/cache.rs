@@ -0,0 +1,558 @@
+//! Caching JSON RPC client.
+//!
+//! This module provides a client that caches results of queries that can't
+//! change, such as blocks by hash or state at a specific block hash. Cache keys
+//! are computed from the method name and serialized parameters, so custom
+//! methods can be cached by configuring a [`Rule`] for them.
+
+use crate::{
+    jsonrpc::{self, Id, Request, Response, Value},
+    method::Method,
+    transport::{Transport, TransportExt as _},
+};
+use serde_json::json;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// A JSON RPC client that caches results of immutable queries.
+///
+/// Note that only successful, non-`null` results are cached, as `null`
+/// usually indicates that something (such as a transaction receipt) does not
+/// exist yet.
+pub struct Cache<T> {
+    inner: T,
+    config: Configuration,
+    entries: Mutex<Entries>,
+    finalized: Mutex<Finalized>,
+}
+
+impl<T> Cache<T> {
+    /// Creates a new caching client around the specified transport.
+    pub fn new(inner: T, config: Configuration) -> Self {
+        Self {
+            inner,
+            config,
+            entries: Mutex::default(),
+            finalized: Mutex::default(),
+        }
+    }
+
+    /// Returns a reference to the inner transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Removes all cached entries.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.map.clear();
+        entries.recency.clear();
+    }
+
+    /// Returns how long the result of a request can be cached for, or `None`
+    /// if it can't be cached.
+    fn lifetime(&self, request: &Request) -> Option<Lifetime> {
+        let latest = self.config.latest_ttl.map(Lifetime::Ttl);
+        match self.config.rules.get(request.method.as_str())? {
+            Rule::Immutable => Some(Lifetime::Forever),
+            Rule::Latest => latest,
+            Rule::Block(index) => match request.params.0.get(index) {
+                None | Some(serde_json::Value::Null) => latest,
+                Some(serde_json::Value::String(block)) if block == "latest" => latest,
+                Some(serde_json::Value::String(block)) if is_hash(block) => Some(Lifetime::Forever),
+                Some(serde_json::Value::Object(block)) => block
+                    .get("blockHash")
+                    .and_then(serde_json::Value::as_str)
+                    .filter(|hash| is_hash(hash))
+                    .map(|_| Lifetime::Forever),
+                _ => None,
+            },
+            Rule::Finalized => Some(Lifetime::Finalized),
+        }
+    }
+
+    fn get(&self, key: &Key, id: Id) -> Option<Response> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.map.get(key)?;
+        if entry
+            .expires
+            .is_some_and(|expires| expires <= Instant::now())
+        {
+            entries.remove(key);
+            return None;
+        }
+        let result = entry.result.clone();
+        entries.touch(key);
+        Some(Response {
+            jsonrpc: jsonrpc::Version::V2,
+            result: Ok(result),
+            id: Some(id),
+        })
+    }
+
+    fn insert(&self, key: Key, result: Value, expires: Option<Instant>) {
+        if self.config.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        entries.remove(&key);
+        while entries.map.len() >= self.config.max_entries {
+            entries.evict();
+        }
+        let used = entries.tick();
+        entries.recency.insert(used, key.clone());
+        entries.map.insert(
+            key,
+            Entry {
+                result,
+                expires,
+                used,
+            },
+        );
+    }
+}
+
+impl<T> Cache<T>
+where
+    T: Transport,
+{
+    async fn store(&self, key: Key, lifetime: Lifetime, response: &Response) {
+        let Ok(result) = &response.result else {
+            return;
+        };
+        if result.0.is_null() {
+            return;
+        }
+
+        let expires = match lifetime {
+            Lifetime::Forever => None,
+            Lifetime::Ttl(ttl) => Some(Instant::now() + ttl),
+            Lifetime::Finalized => {
+                let Some(block) = result.0.get("blockNumber").and_then(number) else {
+                    return;
+                };
+                if !self.is_finalized(block).await {
+                    return;
+                }
+                None
+            }
+        };
+        self.insert(key, result.clone(), expires);
+    }
+
+    /// Returns whether or not the specified block is known to be finalized.
+    ///
+    /// The finalized block number is fetched from the node as needed, at most
+    /// once per configured refresh interval. This includes failed lookups, so
+    /// a node that doesn't support the `finalized` block tag is not queried on
+    /// every call.
+    async fn is_finalized(&self, block: u64) -> bool {
+        {
+            let mut finalized = self.finalized.lock().unwrap();
+            if finalized.number.is_some_and(|number| block <= number) {
+                return true;
+            }
+            if finalized
+                .fetched
+                .is_some_and(|fetched| fetched.elapsed() < self.config.finalized_refresh)
+            {
+                return false;
+            }
+            // Record the lookup before sending it, so that concurrent calls
+            // don't query the node as well.
+            finalized.fetched = Some(Instant::now());
+        }
+
+        let Some(number) = self
+            .inner
+            .call("eth_getBlockByNumber", json!(["finalized", false]))
+            .await
+            .ok()
+            .and_then(|block| block.get("number").and_then(number))
+        else {
+            return false;
+        };
+        let mut finalized = self.finalized.lock().unwrap();
+        finalized.number = Some(finalized.number.map_or(number, |known| known.max(number)));
+        block <= number
+    }
+}
+
+impl<T> Transport for Cache<T>
+where
+    T: Transport,
+{
+    type Error = T::Error;
+
+    async fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        let Some(lifetime) = self.lifetime(&request) else {
+            return self.inner.roundtrip(request).await;
+        };
+
+        let key = Key::new(&request);
+        if let Some(response) = self.get(&key, request.id) {
+            return Ok(response);
+        }
+
+        let response = self.inner.roundtrip(request).await?;
+        self.store(key, lifetime, &response).await;
+        Ok(response)
+    }
+
+    /// Sends a batch of JSON RPC requests.
+    ///
+    /// Cached results are returned directly, and only the remaining requests
+    /// are sent to the node.
+    async fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        let mut responses = Vec::new();
+        let mut uncached = Vec::new();
+        let mut pending = HashMap::new();
+        for request in requests {
+            let Some(lifetime) = self.lifetime(&request) else {
+                uncached.push(request);
+                continue;
+            };
+
+            let key = Key::new(&request);
+            match self.get(&key, request.id) {
+                Some(response) => responses.push(response),
+                None => {
+                    pending.insert(request.id, (key, lifetime));
+                    uncached.push(request);
+                }
+            }
+        }
+        if uncached.is_empty() {
+            return Ok(responses);
+        }
+
+        let fetched = self.inner.roundtrip_batch(uncached).await?;
+        for response in &fetched {
+            if let Some((key, lifetime)) = response.id.and_then(|id| pending.remove(&id)) {
+                self.store(key, lifetime, response).await;
+            }
+        }
+        responses.extend(fetched);
+        Ok(responses)
+    }
+}
+
+/// Cache configuration.
+#[derive(Clone, Debug)]
+pub struct Configuration {
+    /// Caching rules by method name.
+    pub rules: HashMap<String, Rule>,
+    /// The amount of time to cache results for calls made at the `latest`
+    /// block. `None` disables caching of these calls.
+    pub latest_ttl: Option<Duration>,
+    /// The interval at which the finalized block number is refreshed, for
+    /// methods that are cached once their results are finalized. The node is
+    /// queried for the finalized block at most once per interval.
+    pub finalized_refresh: Duration,
+    /// The maximum number of cached entries. Once this is reached, the least
+    /// recently used entries are evicted. Expired entries are evicted when
+    /// they are accessed.
+    pub max_entries: usize,
+}
+
+impl Configuration {
+    /// Sets the caching rule for the specified method.
+    pub fn with_rule<M>(mut self, method: M, rule: Rule) -> Self
+    where
+        M: Method,
+    {
+        self.rules.insert(method.name().into_owned(), rule);
+        self
+    }
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self {
+            rules: [
+                ("eth_blockNumber", Rule::Latest),
+                ("eth_call", Rule::Block(1)),
+                ("eth_chainId", Rule::Immutable),
+                ("eth_getBalance", Rule::Block(1)),
+                ("eth_getBlockByHash", Rule::Immutable),
+                ("eth_getBlockByNumber", Rule::Block(0)),
+                ("eth_getBlockReceipts", Rule::Block(0)),
+                ("eth_getBlockTransactionCountByHash", Rule::Immutable),
+                ("eth_getCode", Rule::Block(1)),
+                ("eth_getProof", Rule::Block(2)),
+                ("eth_getStorageAt", Rule::Block(2)),
+                ("eth_getTransactionByBlockHashAndIndex", Rule::Immutable),
+                ("eth_getTransactionCount", Rule::Block(1)),
+                ("eth_getTransactionReceipt", Rule::Finalized),
+                ("eth_getUncleCountByBlockHash", Rule::Immutable),
+                ("net_version", Rule::Immutable),
+            ]
+            .into_iter()
+            .map(|(method, rule)| (method.to_owned(), rule))
+            .collect(),
+            latest_ttl: None,
+            finalized_refresh: Duration::from_secs(12),
+            max_entries: 10_000,
+        }
+    }
+}
+
+/// A rule describing when the result of a method can be cached.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rule {
+    /// The result never changes.
+    Immutable,
+    /// The result is for the `latest` block, and can be cached for the
+    /// configured `latest` TTL.
+    Latest,
+    /// The result depends on the block parameter at the specified index. The
+    /// result never changes if it is pinned to a block hash, and is cached for
+    /// the configured `latest` TTL if it is for the `latest` block (or the
+    /// block parameter is omitted).
+    Block(usize),
+    /// The result contains a `blockNumber` and never changes once that block
+    /// has been finalized.
+    ///
+    /// Note that checking whether a block is finalized requires an additional
+    /// `eth_getBlockByNumber` call, which is made at most once per configured
+    /// refresh interval.
+    Finalized,
+}
+
+/// A cache key.
+#[derive(Clone, Eq, Hash, PartialEq)]
+struct Key {
+    method: String,
+    params: String,
+}
+
+impl Key {
+    fn new(request: &Request) -> Self {
+        Self {
+            method: request.method.as_str().to_owned(),
+            params: request.params.0.to_string(),
+        }
+    }
+}
+
+/// Cached entries.
+#[derive(Default)]
+struct Entries {
+    map: HashMap<Key, Entry>,
+    /// Keys of the cached entries, ordered by when they were last used.
+    recency: BTreeMap<u64, Key>,
+    /// A logical clock for keeping track of when entries were last used.
+    clock: u64,
+}
+
+impl Entries {
+    /// Advances the clock, returning the new time.
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Marks an entry as used.
+    fn touch(&mut self, key: &Key) {
+        let used = self.tick();
+        let Some(entry) = self.map.get_mut(key) else {
+            return;
+        };
+        if let Some(key) = self.recency.remove(&entry.used) {
+            self.recency.insert(used, key);
+        }
+        entry.used = used;
+    }
+
+    /// Removes an entry.
+    fn remove(&mut self, key: &Key) {
+        if let Some(entry) = self.map.remove(key) {
+            self.recency.remove(&entry.used);
+        }
+    }
+
+    /// Evicts the least recently used entry.
+    fn evict(&mut self) {
+        let Some((_, key)) = self.recency.pop_first() else {
+            return;
+        };
+        self.map.remove(&key);
+    }
+}
+
+/// The last known finalized block.
+#[derive(Default)]
+struct Finalized {
+    number: Option<u64>,
+    fetched: Option<Instant>,
+}
+
+/// A cache entry.
+struct Entry {
+    result: Value,
+    expires: Option<Instant>,
+    used: u64,
+}
+
+/// How long a result can be cached for.
+#[derive(Clone, Copy)]
+enum Lifetime {
+    Forever,
+    Ttl(Duration),
+    Finalized,
+}
+
+/// Returns whether or not a string is a 32-byte hex hash.
+fn is_hash(value: &str) -> bool {
+    value.len() == 66 && value.starts_with("0x")
+}
+
+/// Parses a hex quantity.
+fn number(value: &serde_json::Value) -> Option<u64> {
+    u64::from_str_radix(value.as_str()?.strip_prefix("0x")?, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth,
+        mock::{Mock, ready},
+        types::{BlockId, BlockTag, Digest, Empty},
+    };
+    use ethprim::address;
+
+    /// A node with a finalized block `0x10` and a receipt in block `0x10` and
+    /// `0x11` for transaction hashes `0x11..` and `0x22..` respectively.
+    fn node() -> Mock {
+        let mut mock = Mock::new();
+        mock.expect("eth_getBlockByNumber")
+            .returns(json!({ "number": "0x10" }));
+        mock.expect("eth_getTransactionReceipt")
+            .with_params(json!([Digest([0x11; 32])]))
+            .returns(json!({ "blockNumber": "0x10" }));
+        mock.expect("eth_getTransactionReceipt")
+            .returns(json!({ "blockNumber": "0x11" }));
+        for method in [
+            "eth_blockNumber",
+            "eth_chainId",
+            "eth_getBalance",
+            "eth_getBlockByHash",
+            "net_version",
+        ] {
+            mock.expect(method).returns(json!("0x2a"));
+        }
+        mock
+    }
+
+    #[test]
+    fn caches_immutable_queries() {
+        let client = Cache::new(node(), Configuration::default());
+        for _ in 0..3 {
+            assert_eq!(ready(client.call_np(eth::ChainId)).unwrap(), 42);
+        }
+        assert_eq!(client.inner().requests().len(), 1);
+    }
+
+    #[test]
+    fn caches_queries_pinned_to_block_hash() {
+        let client = Cache::new(node(), Configuration::default());
+        let account = address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41");
+        for _ in 0..3 {
+            ready(client.call(
+                eth::GetBalance,
+                (account, BlockId::Hash(Digest([0xbb; 32]))),
+            ))
+            .unwrap();
+            ready(client.call(eth::GetBalance, (account, BlockTag::Latest.into()))).unwrap();
+        }
+        assert_eq!(client.inner().requests().len(), 4);
+
+        let client = Cache::new(
+            node(),
+            Configuration {
+                latest_ttl: Some(Duration::from_secs(60)),
+                ..Default::default()
+            },
+        );
+        for _ in 0..3 {
+            ready(client.call(eth::GetBalance, (account, BlockTag::Latest.into()))).unwrap();
+            ready(client.call(eth::GetBalance, (account, BlockTag::Safe.into()))).unwrap();
+        }
+        assert_eq!(client.inner().requests().len(), 4);
+    }
+
+    #[test]
+    fn caches_finalized_receipts() {
+        let client = Cache::new(node(), Configuration::default());
+        for _ in 0..3 {
+            for hash in [[0x11; 32], [0x22; 32]] {
+                ready(client.call("eth_getTransactionReceipt", json!([Digest(hash)]))).unwrap();
+            }
+        }
+        // 1 request for finalized receipt, 3 requests for the non-finalized
+        // receipt, and 1 request for the finalized block number.
+        assert_eq!(client.inner().requests().len(), 5);
+    }
+
+    #[test]
+    fn limits_finalized_block_lookups() {
+        let mut node = Mock::new();
+        node.expect("eth_getBlockByNumber")
+            .returns_error(jsonrpc::Error::custom("unsupported block tag"));
+        node.expect("eth_getTransactionReceipt")
+            .returns(json!({ "blockNumber": "0x10" }));
+        let client = Cache::new(
+            node,
+            Configuration {
+                finalized_refresh: Duration::from_secs(60),
+                ..Default::default()
+            },
+        );
+        for _ in 0..3 {
+            ready(client.call("eth_getTransactionReceipt", json!([Digest([0x11; 32])]))).unwrap();
+        }
+        // 3 requests for the receipt, and only 1 failed request for the
+        // finalized block number.
+        assert_eq!(client.inner().requests().len(), 4);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_entries() {
+        let client = Cache::new(
+            node(),
+            Configuration {
+                max_entries: 2,
+                ..Default::default()
+            },
+        );
+        let block = json!([Digest([0xbb; 32]), false]);
+        ready(client.call("eth_chainId", json!([]))).unwrap();
+        ready(client.call("net_version", json!([]))).unwrap();
+        ready(client.call("eth_chainId", json!([]))).unwrap();
+        ready(client.call("eth_getBlockByHash", block.clone())).unwrap();
+        assert_eq!(client.inner().requests().len(), 3);
+
+        // `net_version` was the least recently used entry, so it was evicted to
+        // make room for the block.
+        ready(client.call("eth_chainId", json!([]))).unwrap();
+        ready(client.call("eth_getBlockByHash", block)).unwrap();
+        assert_eq!(client.inner().requests().len(), 3);
+        ready(client.call("net_version", json!([]))).unwrap();
+        assert_eq!(client.inner().requests().len(), 4);
+    }
+
+    #[test]
+    fn caches_batched_requests() {
+        let client = Cache::new(node(), Configuration::default());
+        ready(client.call_np(eth::ChainId)).unwrap();
+        assert_eq!(
+            ready(client.batch(((eth::ChainId, Empty), (eth::BlockNumber, Empty)))).unwrap(),
+            (42, 42),
+        );
+        assert_eq!(client.inner().requests().len(), 2);
+    }
+}
@@ -0,0 +1,364 @@
+//! Record and replay JSON RPC transports.
+//!
+//! This module provides a [`Recorder`] that records all requests and responses
+//! sent over a transport to a [`Cassette`], and a [`Replayer`] that serves
+//! these recorded responses without any network access. This allows real node
+//! interactions to be captured once and then used for running tests offline
+//! and deterministically.
+//!
+//! Cassettes are stored as JSON files containing the recorded requests and
+//! responses as they were sent over the wire.
+
+use crate::{
+    jsonrpc::{self, Id, JsonError, Request, Response, batch},
+    transport::{BlockingTransport, Transport},
+};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path, sync::Mutex};
+use thiserror::Error;
+
+/// A recording of JSON RPC interactions.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Cassette {
+    /// The recorded interactions, in the order that they happened.
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Loads a cassette from a file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let data = fs::read(path)?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Saves the cassette to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let data = serde_json::to_vec_pretty(self)?;
+        fs::write(path, data)
+    }
+}
+
+/// A recorded JSON RPC interaction.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interaction {
+    /// A single request and its response.
+    Call {
+        request: Request,
+        response: Response,
+    },
+    /// A batch of requests and their responses.
+    Batch {
+        requests: Vec<Request>,
+        responses: Vec<Response>,
+    },
+}
+
+/// A transport that records all interactions with an inner transport.
+pub struct Recorder<T> {
+    inner: T,
+    cassette: Mutex<Cassette>,
+}
+
+impl<T> Recorder<T> {
+    /// Creates a new recorder around the specified transport.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            cassette: Mutex::default(),
+        }
+    }
+
+    /// Returns a reference to the inner transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a copy of the interactions recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+
+    /// Saves the interactions recorded so far to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.cassette.lock().unwrap().save(path)
+    }
+
+    fn record(&self, interaction: Interaction) {
+        self.cassette.lock().unwrap().interactions.push(interaction);
+    }
+}
+
+impl<T> Transport for Recorder<T>
+where
+    T: Transport,
+{
+    type Error = T::Error;
+
+    async fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        let response = self.inner.roundtrip(request.clone()).await?;
+        self.record(Interaction::Call {
+            request,
+            response: response.clone(),
+        });
+        Ok(response)
+    }
+
+    async fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        let responses = self.inner.roundtrip_batch(requests.clone()).await?;
+        self.record(Interaction::Batch {
+            requests,
+            responses: responses.clone(),
+        });
+        Ok(responses)
+    }
+}
+
+impl<T> BlockingTransport for Recorder<T>
+where
+    T: BlockingTransport,
+{
+    type Error = T::Error;
+
+    fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        let response = self.inner.roundtrip(request.clone())?;
+        self.record(Interaction::Call {
+            request,
+            response: response.clone(),
+        });
+        Ok(response)
+    }
+
+    fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        let responses = self.inner.roundtrip_batch(requests.clone())?;
+        self.record(Interaction::Batch {
+            requests,
+            responses: responses.clone(),
+        });
+        Ok(responses)
+    }
+}
+
+/// A transport that serves responses from a [`Cassette`].
+///
+/// Requests are matched against recorded interactions by method and
+/// parameters, ignoring request IDs. Each recorded interaction is only served
+/// once, so repeated requests are served the recorded responses in the order
+/// that they were recorded.
+pub struct Replayer {
+    interactions: Mutex<Vec<Option<Interaction>>>,
+}
+
+impl Replayer {
+    /// Creates a new replayer for the specified cassette.
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            interactions: Mutex::new(cassette.interactions.into_iter().map(Some).collect()),
+        }
+    }
+
+    /// Creates a new replayer for the cassette stored in the specified file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(Cassette::load(path)?))
+    }
+
+    /// Returns the number of recorded interactions that have not yet been
+    /// replayed.
+    pub fn remaining(&self) -> usize {
+        self.interactions.lock().unwrap().iter().flatten().count()
+    }
+
+    /// Takes the first unplayed interaction matching the specified predicate.
+    fn take(&self, predicate: impl Fn(&Interaction) -> bool) -> Option<Interaction> {
+        self.interactions
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|interaction| interaction.as_ref().is_some_and(&predicate))?
+            .take()
+    }
+
+    fn replay(&self, request: Request) -> Result<Response, Error> {
+        let interaction = self.take(|interaction| match interaction {
+            Interaction::Call {
+                request: recorded, ..
+            } => same(recorded, &request),
+            _ => false,
+        });
+        let Some(Interaction::Call { response, .. }) = interaction else {
+            return Err(Error::Missing(describe(&request)));
+        };
+
+        Ok(Response {
+            id: Some(request.id),
+            ..response
+        })
+    }
+
+    fn replay_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Error> {
+        let interaction = self.take(|interaction| match interaction {
+            Interaction::Batch {
+                requests: recorded, ..
+            } => {
+                recorded.len() == requests.len()
+                    && recorded.iter().zip(&requests).all(|(a, b)| same(a, b))
+            }
+            _ => false,
+        });
+        let Some(Interaction::Batch {
+            requests: recorded,
+            responses,
+        }) = interaction
+        else {
+            let batch = requests.iter().map(describe).collect::<Vec<_>>();
+            return Err(Error::Missing(format!("batch [{}]", batch.join(", "))));
+        };
+
+        // Responses are not necessarily in the same order as their requests,
+        // so map recorded IDs to the IDs of the replayed requests by position.
+        let ids = recorded
+            .iter()
+            .zip(&requests)
+            .map(|(recorded, request)| (recorded.id, request.id))
+            .collect::<Vec<(Id, Id)>>();
+        Ok(responses
+            .into_iter()
+            .map(|response| Response {
+                id: response.id.and_then(|id| {
+                    ids.iter()
+                        .find(|(recorded, _)| *recorded == id)
+                        .map(|(_, id)| *id)
+                }),
+                ..response
+            })
+            .collect())
+    }
+}
+
+impl Transport for Replayer {
+    type Error = Error;
+
+    async fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        self.replay(request)
+    }
+
+    async fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        self.replay_batch(requests)
+    }
+}
+
+impl BlockingTransport for Replayer {
+    type Error = Error;
+
+    fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        self.replay(request)
+    }
+
+    fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        self.replay_batch(requests)
+    }
+}
+
+/// Returns whether or not two requests are the same, ignoring their IDs.
+fn same(a: &Request, b: &Request) -> bool {
+    a.method.as_str() == b.method.as_str() && a.params.0 == b.params.0
+}
+
+/// Returns a human readable description of a request.
+fn describe(request: &Request) -> String {
+    format!("{}({})", request.method.as_str(), request.params.0)
+}
+
+/// A replay transport error.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON error: {0}")]
+    Json(#[from] JsonError),
+    #[error("no recorded interaction for {0}")]
+    Missing(String),
+    #[error(transparent)]
+    Rpc(#[from] jsonrpc::Error),
+    #[error(transparent)]
+    Batch(#[from] batch::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth,
+        mock::{Mock, ready},
+        transport::{BlockingTransportExt, TransportExt},
+        types::{BlockTag, Empty},
+    };
+    use ethprim::{address, uint};
+    use std::env;
+
+    #[test]
+    fn record_and_replay() {
+        let account = address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41");
+        let mut mock = Mock::new();
+        mock.expect(eth::GetBalance).returns(uint!("42"));
+        mock.expect(eth::BlockNumber).returns(1);
+        mock.expect(eth::ChainId).returns(2);
+
+        let recorder = Recorder::new(mock);
+        assert_eq!(
+            BlockingTransportExt::call(
+                &recorder,
+                eth::GetBalance,
+                (account, BlockTag::Latest.into())
+            )
+            .unwrap(),
+            uint!("42"),
+        );
+        assert_eq!(
+            ready(TransportExt::batch(
+                &recorder,
+                ((eth::BlockNumber, Empty), (eth::ChainId, Empty))
+            ))
+            .unwrap(),
+            (1, 2),
+        );
+
+        let path = env::temp_dir().join(format!("ethrpc-cassette-{}.json", std::process::id()));
+        recorder.save(&path).unwrap();
+        let replayer = Replayer::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(replayer.remaining(), 2);
+        assert_eq!(
+            ready(TransportExt::call(
+                &replayer,
+                eth::GetBalance,
+                (account, BlockTag::Latest.into())
+            ))
+            .unwrap(),
+            uint!("42"),
+        );
+        assert!(matches!(
+            BlockingTransportExt::call(
+                &replayer,
+                eth::GetBalance,
+                (account, BlockTag::Safe.into())
+            ),
+            Err(Error::Missing(_)),
+        ));
+        assert!(matches!(
+            BlockingTransportExt::batch(
+                &replayer,
+                ((eth::ChainId, Empty), (eth::BlockNumber, Empty))
+            ),
+            Err(Error::Missing(_)),
+        ));
+        assert_eq!(
+            BlockingTransportExt::batch(
+                &replayer,
+                ((eth::BlockNumber, Empty), (eth::ChainId, Empty))
+            )
+            .unwrap(),
+            (1, 2),
+        );
+        assert_eq!(replayer.remaining(), 0);
+    }
+}
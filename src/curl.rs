@@ -1,11 +1,14 @@
 //! HTTP JSON RPC client implemented with curl.
 
 use crate::{
+    instrumentation::{Span, Tracer},
     jsonrpc::{
         self, JsonError,
         batch::{self, Batch},
     },
     method::Method,
+    metrics::{Metrics, Probe},
+    transport::{BlockingTransport, BlockingTransportExt},
     types::Empty,
 };
 pub use curl;
@@ -15,12 +18,15 @@ use std::{
     cell::RefCell,
     env,
     io::{Read, Write},
+    sync::Arc,
 };
 use thiserror::Error;
 
 /// An Ethereum RPC HTTP client.
 pub struct Client {
     handle: RefCell<Easy>,
+    metrics: Option<Arc<dyn Metrics>>,
+    tracer: Tracer,
 }
 
 impl Client {
@@ -39,9 +45,25 @@ impl Client {
     pub fn with_handle(handle: Easy) -> Self {
         Self {
             handle: RefCell::new(handle),
+            metrics: None,
+            tracer: Tracer::default(),
         }
     }
 
+    /// Configures the client to report metrics for all calls.
+    pub fn with_metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Configures which methods have their request and response bodies
+    /// redacted from tracing events.
+    #[cfg(feature = "tracing")]
+    pub fn with_redaction(mut self, redaction: crate::Redaction) -> Self {
+        self.tracer = Tracer::new(redaction);
+        self
+    }
+
     /// Creates a new JSON RPC HTTP client from the environment. This method
     /// uses the `ETHRPC` environment variable. This is useful for testing.
     ///
@@ -53,12 +75,34 @@ impl Client {
         Self::new(env::var("ETHRPC").expect("missing ETHRPC environment variable")).unwrap()
     }
 
-    fn roundtrip<T, R>(&self, request: T) -> Result<R, Error>
+    fn roundtrip(&self, request: jsonrpc::Request) -> Result<jsonrpc::Response, Error> {
+        let span = self.tracer.call(&request);
+        let probe = Probe::start(self.metrics.as_deref());
+        let response = span.in_scope(|| self.send(&request, &span, &probe));
+        span.finish_call(&response);
+        probe.finish_call(&request, &response);
+        response
+    }
+
+    fn roundtrip_batch(
+        &self,
+        requests: Vec<jsonrpc::Request>,
+    ) -> Result<Vec<jsonrpc::Response>, Error> {
+        let span = self.tracer.batch(&requests);
+        let probe = Probe::start(self.metrics.as_deref());
+        let responses = span.in_scope(|| self.send(&requests, &span, &probe));
+        span.finish_batch(&requests, &responses);
+        probe.finish_batch(&requests, &responses);
+        responses
+    }
+
+    fn send<T, R>(&self, request: &T, span: &Span, probe: &Probe) -> Result<R, Error>
     where
         T: Serialize,
         R: DeserializeOwned,
     {
-        let request = serde_json::to_vec(&request)?;
+        let request = serde_json::to_vec(request)?;
+        probe.sent(request.len());
 
         let mut handle = self
             .handle
@@ -83,6 +127,9 @@ impl Client {
         }
 
         let status = handle.response_code()?;
+        span.status(status);
+        probe.status(status);
+        probe.received(response.len());
         if !(200..300).contains(&status) {
             return Err(Error::Status(
                 status,
@@ -95,37 +142,59 @@ impl Client {
     }
 
     /// Executes a JSON RPC call.
+    ///
+    /// This is equivalent to [`BlockingTransportExt::call`].
     pub fn call<M>(&self, method: M, params: M::Params) -> Result<M::Result, Error>
     where
         M: Method + Serialize,
     {
-        jsonrpc::call(method, params, |request| self.roundtrip(request))
+        BlockingTransportExt::call(self, method, params)
     }
 
     /// Executes a JSON RPC call with empty parameters.
+    ///
+    /// This is equivalent to [`BlockingTransportExt::call_np`].
     pub fn exec<M>(&self, method: M) -> Result<M::Result, Error>
     where
         M: Method<Params = Empty> + Serialize,
     {
-        jsonrpc::call(method, Empty, |request| self.roundtrip(request))
+        BlockingTransportExt::call_np(self, method)
     }
 
     /// Executes a JSON RPC batch request.
+    ///
+    /// This is equivalent to [`BlockingTransportExt::batch`].
     pub fn batch<B>(&self, batch: B) -> Result<B::Values, Error>
     where
         B: Batch,
     {
-        batch::call(batch, |requests| self.roundtrip(requests))
+        BlockingTransportExt::batch(self, batch)
     }
 
     /// Executes a JSON RPC batch request, returning individual JSON RPC results
-    /// for each batched requests. This allows fine-grained error handling
-    /// for individual methods.
+    /// for each batched requests.
+    ///
+    /// This is equivalent to [`BlockingTransportExt::try_batch`].
     pub fn try_batch<B>(&self, batch: B) -> Result<B::Results, Error>
     where
         B: Batch,
     {
-        batch::try_call(batch, |requests| self.roundtrip(requests))
+        BlockingTransportExt::try_batch(self, batch)
+    }
+}
+
+impl BlockingTransport for Client {
+    type Error = Error;
+
+    fn roundtrip(&self, request: jsonrpc::Request) -> Result<jsonrpc::Response, Error> {
+        Client::roundtrip(self, request)
+    }
+
+    fn roundtrip_batch(
+        &self,
+        requests: Vec<jsonrpc::Request>,
+    ) -> Result<Vec<jsonrpc::Response>, Error> {
+        Client::roundtrip_batch(self, requests)
     }
 }
 
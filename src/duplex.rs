@@ -0,0 +1,916 @@
+//! JSON RPC over persistent duplex connections.
+//!
+//! This module implements the machinery shared by transports that keep a
+//! long-lived connection to a node (such as WebSockets and IPC sockets).
+//! Concurrent JSON RPC calls are multiplexed over a single connection, with
+//! responses matched to their requests by JSON RPC ID. If the connection
+//! drops, it is re-established on the next call.
+
+use crate::{
+    eth,
+    jsonrpc::{self, Id, JsonError, batch},
+    types::{SubscriptionId, SubscriptionKind},
+};
+use futures::Stream;
+use serde::{Deserialize, de::DeserializeOwned};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+
+/// A persistent connection to a node.
+pub(crate) trait Connection: Sized + Send + 'static {
+    /// The endpoint used for establishing connections.
+    type Endpoint: Send + 'static;
+    /// The client error type.
+    type Error: ClientError;
+
+    /// Connects to the node at the specified endpoint.
+    fn connect(endpoint: &Self::Endpoint)
+    -> impl Future<Output = Result<Self, Self::Error>> + Send;
+
+    /// Sends a message to the node.
+    fn send(&mut self, message: String) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Receives the next message from the node, returning `None` if the
+    /// connection was closed.
+    ///
+    /// This method must be cancel safe.
+    fn receive(&mut self) -> impl Future<Output = Option<Vec<u8>>> + Send;
+
+    /// Gracefully closes the connection.
+    fn close(self) -> impl Future<Output = ()> + Send;
+}
+
+/// The error type of a client using a persistent connection.
+pub trait ClientError:
+    From<JsonError> + From<jsonrpc::Error> + From<batch::Error> + Send + 'static
+{
+    /// Returns the error for calls that were interrupted by the connection
+    /// closing.
+    fn closed() -> Self;
+
+    /// Returns the error for calls with a request ID that is already in use by
+    /// another in-flight call.
+    fn duplicate_id(id: Id) -> Self;
+}
+
+/// A client that multiplexes calls over a persistent connection.
+pub(crate) struct Client<C>
+where
+    C: Connection,
+{
+    calls: mpsc::UnboundedSender<Call<C::Error>>,
+}
+
+enum Call<E> {
+    Single(
+        jsonrpc::Request,
+        oneshot::Sender<Result<jsonrpc::Response, E>>,
+    ),
+    Batch(
+        Vec<jsonrpc::Request>,
+        oneshot::Sender<Result<Vec<jsonrpc::Response>, E>>,
+    ),
+    Subscribe(
+        jsonrpc::Request,
+        oneshot::Sender<Result<(jsonrpc::Response, Notifications<E>), E>>,
+    ),
+    Unsubscribe(
+        jsonrpc::Request,
+        SubscriptionId,
+        oneshot::Sender<Result<jsonrpc::Response, E>>,
+    ),
+}
+
+type Notifications<E> = mpsc::UnboundedReceiver<Result<jsonrpc::Value, E>>;
+
+impl<C> Client<C>
+where
+    C: Connection,
+{
+    /// Creates a new client for the specified endpoint.
+    ///
+    /// The connection is established lazily on the first call. Note that this
+    /// spawns a background worker task that owns the connection, and must be
+    /// called from within a Tokio runtime.
+    pub fn new(endpoint: C::Endpoint) -> Self {
+        let (calls, receiver) = mpsc::unbounded_channel();
+        tokio::task::spawn(Worker::<C>::new(endpoint).run(receiver));
+        Self { calls }
+    }
+
+    pub async fn roundtrip(
+        &self,
+        request: jsonrpc::Request,
+    ) -> Result<jsonrpc::Response, C::Error> {
+        dispatch(&self.calls, |sender| Call::Single(request, sender)).await
+    }
+
+    pub async fn roundtrip_batch(
+        &self,
+        requests: Vec<jsonrpc::Request>,
+    ) -> Result<Vec<jsonrpc::Response>, C::Error> {
+        dispatch(&self.calls, |sender| Call::Batch(requests, sender)).await
+    }
+
+    pub async fn subscribe<T>(
+        &self,
+        kind: SubscriptionKind,
+    ) -> Result<Subscription<T, C::Error>, C::Error> {
+        let request = jsonrpc::Request::new(eth::Subscribe, kind)?;
+        let (response, notifications) =
+            dispatch(&self.calls, |sender| Call::Subscribe(request, sender)).await?;
+        let id = response.result::<eth::Subscribe>()??;
+
+        Ok(Subscription {
+            id,
+            calls: self.calls.clone(),
+            notifications,
+            _item: PhantomData,
+        })
+    }
+}
+
+/// A subscription to node events.
+///
+/// Subscription notifications are received as a [`Stream`] of items. The
+/// stream ends once the subscription is cancelled with
+/// [`Subscription::unsubscribe`]. If the connection to the node drops, the
+/// stream yields an error and ends.
+///
+/// Dropping a subscription without unsubscribing will cause it to be
+/// cancelled once the next notification for it is received.
+pub struct Subscription<T, E> {
+    id: SubscriptionId,
+    calls: mpsc::UnboundedSender<Call<E>>,
+    notifications: Notifications<E>,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T, E> Subscription<T, E>
+where
+    E: ClientError,
+{
+    /// Returns the node-assigned subscription ID.
+    pub fn id(&self) -> &SubscriptionId {
+        &self.id
+    }
+
+    /// Cancels the subscription.
+    ///
+    /// The subscription stream ends after yielding any notifications that
+    /// were received before it was cancelled.
+    pub async fn unsubscribe(&mut self) -> Result<bool, E> {
+        jsonrpc::call_async(eth::Unsubscribe, (self.id.clone(),), |request| {
+            dispatch(&self.calls, |sender| {
+                Call::Unsubscribe(request, self.id.clone(), sender)
+            })
+        })
+        .await
+    }
+}
+
+impl<T, E> Stream for Subscription<T, E>
+where
+    T: DeserializeOwned,
+    E: From<JsonError>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.notifications
+            .poll_recv(cx)
+            .map(|notification| notification.map(|result| Ok(result?.deserialize()?)))
+    }
+}
+
+/// Dispatches a call to the background worker and waits for its result.
+///
+/// If the background worker stopped (which only happens if it panicked), the
+/// call fails as if the connection was closed.
+async fn dispatch<T, E>(
+    calls: &mpsc::UnboundedSender<Call<E>>,
+    call: impl FnOnce(oneshot::Sender<Result<T, E>>) -> Call<E>,
+) -> Result<T, E>
+where
+    E: ClientError,
+{
+    let (sender, receiver) = oneshot::channel();
+    if calls.send(call(sender)).is_err() {
+        return Err(E::closed());
+    }
+    receiver.await.unwrap_or_else(|_| Err(E::closed()))
+}
+
+/// A call that is waiting for a response from the node.
+enum Pending<E> {
+    Single(oneshot::Sender<Result<jsonrpc::Response, E>>),
+    Batch(Vec<Id>, oneshot::Sender<Result<Vec<jsonrpc::Response>, E>>),
+    Subscribe(oneshot::Sender<Result<(jsonrpc::Response, Notifications<E>), E>>),
+}
+
+impl<E> Pending<E> {
+    fn fail(self, err: E) {
+        match self {
+            Self::Single(sender) => {
+                let _ = sender.send(Err(err));
+            }
+            Self::Batch(_, sender) => {
+                let _ = sender.send(Err(err));
+            }
+            Self::Subscribe(sender) => {
+                let _ = sender.send(Err(err));
+            }
+        }
+    }
+}
+
+/// A message received from the node.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Incoming {
+    Single(jsonrpc::Response),
+    Batch(Vec<jsonrpc::Response>),
+    Notification(jsonrpc::Notification),
+}
+
+/// Parameters of an `eth_subscription` notification.
+#[derive(Deserialize)]
+struct SubscriptionParams {
+    subscription: SubscriptionId,
+    result: jsonrpc::Value,
+}
+
+struct Worker<C>
+where
+    C: Connection,
+{
+    endpoint: C::Endpoint,
+    connection: Option<C>,
+    /// Calls that are waiting for a response, keyed by the ID of their first
+    /// request.
+    pending: HashMap<Id, Pending<C::Error>>,
+    /// The key of the pending call for every in-flight request ID. All
+    /// requests of a batch map to the same pending call.
+    in_flight: HashMap<Id, Id>,
+    subscriptions: HashMap<SubscriptionId, mpsc::UnboundedSender<Result<jsonrpc::Value, C::Error>>>,
+}
+
+impl<C> Worker<C>
+where
+    C: Connection,
+{
+    fn new(endpoint: C::Endpoint) -> Self {
+        Self {
+            endpoint,
+            connection: None,
+            pending: HashMap::new(),
+            in_flight: HashMap::new(),
+            subscriptions: HashMap::new(),
+        }
+    }
+
+    async fn run(mut self, mut calls: mpsc::UnboundedReceiver<Call<C::Error>>) {
+        loop {
+            let Some(connection) = self.connection.as_mut() else {
+                // There is no connection, so wait for a call before connecting
+                // to the node.
+                let Some(call) = calls.recv().await else {
+                    return;
+                };
+                match C::connect(&self.endpoint).await {
+                    Ok(connection) => {
+                        self.connection = Some(connection);
+                        self.send(call).await;
+                    }
+                    Err(err) => match call {
+                        Call::Single(_, sender) | Call::Unsubscribe(_, _, sender) => {
+                            Pending::Single(sender).fail(err)
+                        }
+                        Call::Batch(_, sender) => Pending::Batch(Vec::new(), sender).fail(err),
+                        Call::Subscribe(_, sender) => Pending::Subscribe(sender).fail(err),
+                    },
+                }
+                continue;
+            };
+
+            tokio::select! {
+                call = calls.recv() => match call {
+                    Some(call) => self.send(call).await,
+                    None => {
+                        if let Some(connection) = self.connection.take() {
+                            connection.close().await;
+                        }
+                        return;
+                    }
+                },
+                message = connection.receive() => match message {
+                    Some(message) => self.receive(&message).await,
+                    None => self.disconnect(),
+                },
+            }
+        }
+    }
+
+    async fn send(&mut self, call: Call<C::Error>) {
+        let (ids, message, pending) = match call {
+            Call::Single(request, sender) => (
+                vec![request.id],
+                serde_json::to_string(&request),
+                Pending::Single(sender),
+            ),
+            Call::Batch(requests, sender) => {
+                // Empty batches are invalid JSON RPC requests, so there is no
+                // need to bother the node with them.
+                if requests.is_empty() {
+                    let _ = sender.send(Ok(Vec::new()));
+                    return;
+                }
+                let ids = requests
+                    .iter()
+                    .map(|request| request.id)
+                    .collect::<Vec<_>>();
+                (
+                    ids.clone(),
+                    serde_json::to_string(&requests),
+                    Pending::Batch(ids, sender),
+                )
+            }
+            Call::Subscribe(request, sender) => (
+                vec![request.id],
+                serde_json::to_string(&request),
+                Pending::Subscribe(sender),
+            ),
+            Call::Unsubscribe(request, subscription, sender) => {
+                self.subscriptions.remove(&subscription);
+                (
+                    vec![request.id],
+                    serde_json::to_string(&request),
+                    Pending::Single(sender),
+                )
+            }
+        };
+        // Responses are matched to calls by ID, so a call that reuses the ID of
+        // an in-flight call (or of another request in the same batch) would
+        // steal its response.
+        if let Some(id) = self.duplicate(&ids) {
+            return pending.fail(C::Error::duplicate_id(id));
+        }
+        let message = match message {
+            Ok(message) => message,
+            Err(err) => return pending.fail(JsonError::from(err).into()),
+        };
+
+        let key = ids[0];
+        self.track(key, pending);
+        if let Err(err) = self.write(message).await {
+            if let Some(pending) = self.take(key) {
+                pending.fail(err);
+            }
+            self.disconnect();
+        }
+    }
+
+    /// Returns the first request ID that is already in use, either by a
+    /// pending call or by an earlier request of the same call.
+    fn duplicate(&self, ids: &[Id]) -> Option<Id> {
+        let mut seen = HashSet::new();
+        ids.iter()
+            .copied()
+            .find(|id| self.in_flight.contains_key(id) || !seen.insert(*id))
+    }
+
+    /// Tracks a pending call under the specified key.
+    fn track(&mut self, key: Id, pending: Pending<C::Error>) {
+        match &pending {
+            Pending::Batch(ids, _) => {
+                for id in ids {
+                    self.in_flight.insert(*id, key);
+                }
+            }
+            _ => {
+                self.in_flight.insert(key, key);
+            }
+        }
+        self.pending.insert(key, pending);
+    }
+
+    /// Takes the pending call that the request with the specified ID belongs
+    /// to, so that it can be completed.
+    fn take(&mut self, id: Id) -> Option<Pending<C::Error>> {
+        let key = self.in_flight.get(&id).copied()?;
+        let pending = self.pending.remove(&key)?;
+        match &pending {
+            Pending::Batch(ids, _) => {
+                for id in ids {
+                    self.in_flight.remove(id);
+                }
+            }
+            _ => {
+                self.in_flight.remove(&key);
+            }
+        }
+        Some(pending)
+    }
+
+    /// Fails calls for an error response without an ID.
+    ///
+    /// Nodes respond to requests that they can't process (such as invalid
+    /// batches) with an error without an ID. Since it isn't possible to know
+    /// which call it belongs to, fail all pending batches, or all pending calls
+    /// if there are none.
+    fn fail_unknown(&mut self, err: jsonrpc::Error) {
+        let batches = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| matches!(pending, Pending::Batch(..)))
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        let keys = if batches.is_empty() {
+            self.pending.keys().copied().collect()
+        } else {
+            batches
+        };
+        for key in keys {
+            if let Some(pending) = self.take(key) {
+                pending.fail(err.clone().into());
+            }
+        }
+    }
+
+    async fn write(&mut self, message: String) -> Result<(), C::Error> {
+        self.connection
+            .as_mut()
+            .expect("missing node connection")
+            .send(message)
+            .await
+    }
+
+    async fn receive(&mut self, message: &[u8]) {
+        match serde_json::from_slice(message) {
+            Ok(Incoming::Single(jsonrpc::Response {
+                result: Err(err),
+                id: None,
+                ..
+            })) => self.fail_unknown(err),
+            Ok(Incoming::Single(response)) => {
+                let Some(pending) = response.id.and_then(|id| self.take(id)) else {
+                    return;
+                };
+                match pending {
+                    Pending::Single(sender) => {
+                        let _ = sender.send(Ok(response));
+                    }
+                    Pending::Subscribe(sender) => {
+                        // Register the subscription before any of its
+                        // notifications can be received, so that none are
+                        // missed.
+                        let (notifications, receiver) = mpsc::unbounded_channel();
+                        if let Ok(id) =
+                            response.result.as_ref().map_err(drop).and_then(|value| {
+                                SubscriptionId::deserialize(&value.0).map_err(drop)
+                            })
+                        {
+                            self.subscriptions.insert(id, notifications);
+                        }
+                        let _ = sender.send(Ok((response, receiver)));
+                    }
+                    pending => pending.fail(batch::Error.into()),
+                }
+            }
+            Ok(Incoming::Batch(responses)) => {
+                // Every request of a batch maps to the same pending call, and
+                // responses may be returned in any order, so look for any
+                // response that belongs to a pending batch.
+                let key = responses
+                    .iter()
+                    .filter_map(|response| self.in_flight.get(&response.id?))
+                    .find(|key| matches!(self.pending.get(key), Some(Pending::Batch(..))))
+                    .copied();
+                if let Some(pending) = key.and_then(|key| self.take(key)) {
+                    if let Pending::Batch(_, sender) = pending {
+                        let _ = sender.send(Ok(responses));
+                    }
+                    return;
+                }
+
+                // A batch of errors without IDs is a rejection of a whole batch
+                // that can't be matched to any call.
+                if responses.iter().all(|response| response.id.is_none())
+                    && let Some(err) = responses
+                        .into_iter()
+                        .find_map(|response| response.result.err())
+                {
+                    self.fail_unknown(err);
+                }
+            }
+            Ok(Incoming::Notification(notification)) => {
+                if notification.method.as_str() != "eth_subscription" {
+                    return;
+                }
+                let Ok(params) = notification.params.deserialize::<SubscriptionParams>() else {
+                    return;
+                };
+                let Some(subscription) = self.subscriptions.get(&params.subscription) else {
+                    return;
+                };
+                if subscription.send(Ok(params.result)).is_err() {
+                    // The subscription was dropped without unsubscribing, so
+                    // cancel it now. We don't care about the response.
+                    self.subscriptions.remove(&params.subscription);
+                    let Ok(message) =
+                        jsonrpc::Request::new(eth::Unsubscribe, (params.subscription,))
+                            .and_then(|request| Ok(serde_json::to_string(&request)?))
+                    else {
+                        return;
+                    };
+                    if self.write(message).await.is_err() {
+                        self.disconnect();
+                    }
+                }
+            }
+            Err(_) => (),
+        }
+    }
+
+    fn disconnect(&mut self) {
+        self.connection = None;
+        self.in_flight.clear();
+        for (_, pending) in self.pending.drain() {
+            pending.fail(C::Error::closed());
+        }
+        for (_, subscription) in self.subscriptions.drain() {
+            let _ = subscription.send(Err(C::Error::closed()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Empty;
+    use futures::StreamExt as _;
+    use serde_json::json;
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+    };
+    use thiserror::Error;
+
+    /// An in-memory connection to a node that is scripted by the test.
+    struct Pipe {
+        requests: mpsc::UnboundedSender<String>,
+        responses: mpsc::UnboundedReceiver<Vec<u8>>,
+    }
+
+    /// The node side of a [`Pipe`].
+    struct Node {
+        requests: mpsc::UnboundedReceiver<String>,
+        responses: mpsc::UnboundedSender<Vec<u8>>,
+    }
+
+    impl Node {
+        async fn respond(&mut self, response: &str) {
+            self.requests.recv().await.unwrap();
+            self.send(response);
+        }
+
+        /// Receives the next request and responds to it with the specified
+        /// result, returning the request.
+        async fn reply(&mut self, result: serde_json::Value) -> serde_json::Value {
+            let request = self.request().await;
+            self.send(
+                &json!({ "jsonrpc": "2.0", "result": result, "id": request["id"] }).to_string(),
+            );
+            request
+        }
+
+        async fn request(&mut self) -> serde_json::Value {
+            serde_json::from_str(&self.requests.recv().await.unwrap()).unwrap()
+        }
+
+        fn send(&self, message: &str) {
+            self.responses.send(message.as_bytes().to_vec()).unwrap();
+        }
+    }
+
+    fn connection() -> (Client<Pipe>, Node) {
+        let (client, mut nodes) = connections(1);
+        (client, nodes.pop().unwrap())
+    }
+
+    /// Creates a client that connects to each of the returned nodes in order.
+    fn connections(count: usize) -> (Client<Pipe>, Vec<Node>) {
+        let (pipes, nodes) = (0..count)
+            .map(|_| {
+                let (requests, node_requests) = mpsc::unbounded_channel();
+                let (node_responses, responses) = mpsc::unbounded_channel();
+                (
+                    Pipe {
+                        requests,
+                        responses,
+                    },
+                    Node {
+                        requests: node_requests,
+                        responses: node_responses,
+                    },
+                )
+            })
+            .unzip();
+        (Client::new(Mutex::new(pipes)), nodes)
+    }
+
+    impl Connection for Pipe {
+        type Endpoint = Mutex<VecDeque<Pipe>>;
+        type Error = Error;
+
+        async fn connect(endpoint: &Self::Endpoint) -> Result<Self, Error> {
+            endpoint.lock().unwrap().pop_front().ok_or(Error::Closed)
+        }
+
+        async fn send(&mut self, message: String) -> Result<(), Error> {
+            self.requests.send(message).map_err(|_| Error::Closed)
+        }
+
+        async fn receive(&mut self) -> Option<Vec<u8>> {
+            self.responses.recv().await
+        }
+
+        async fn close(self) {}
+    }
+
+    #[derive(Debug, Error)]
+    enum Error {
+        #[error(transparent)]
+        Json(#[from] JsonError),
+        #[error("connection closed")]
+        Closed,
+        #[error("duplicate ID")]
+        DuplicateId(Id),
+        #[error(transparent)]
+        Rpc(#[from] jsonrpc::Error),
+        #[error(transparent)]
+        Batch(#[from] batch::Error),
+    }
+
+    impl ClientError for Error {
+        fn closed() -> Self {
+            Self::Closed
+        }
+
+        fn duplicate_id(id: Id) -> Self {
+            Self::DuplicateId(id)
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_duplicate_ids() {
+        let (client, mut node) = connection();
+        let client = Arc::new(client);
+        let request = jsonrpc::Request::new(eth::BlockNumber, Empty).unwrap();
+        let id = request.id;
+
+        let first = tokio::spawn({
+            let client = client.clone();
+            let request = request.clone();
+            async move { client.roundtrip(request).await }
+        });
+        let message = node.requests.recv().await.unwrap();
+        assert!(message.contains(r#""method":"eth_blockNumber""#));
+
+        assert!(matches!(
+            client.roundtrip(request).await,
+            Err(Error::DuplicateId(duplicate)) if duplicate == id,
+        ));
+
+        node.send(&format!(
+            r#"{{"jsonrpc":"2.0","result":"0x2a","id":{}}}"#,
+            id.0
+        ));
+        let response = first.await.unwrap().unwrap();
+        assert_eq!(response.result::<eth::BlockNumber>().unwrap().unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn rejects_ids_in_use_by_batches() {
+        let (client, mut node) = connection();
+        let client = Arc::new(client);
+        let requests = vec![
+            jsonrpc::Request::new(eth::BlockNumber, Empty).unwrap(),
+            jsonrpc::Request::new(eth::ChainId, Empty).unwrap(),
+        ];
+        let second = requests[1].clone();
+
+        let batch = tokio::spawn({
+            let client = client.clone();
+            let requests = requests.clone();
+            async move { client.roundtrip_batch(requests).await }
+        });
+        node.request().await;
+
+        assert!(matches!(
+            client.roundtrip(second.clone()).await,
+            Err(Error::DuplicateId(duplicate)) if duplicate == second.id,
+        ));
+        assert!(matches!(
+            client.roundtrip_batch(vec![second.clone(), second.clone()]).await,
+            Err(Error::DuplicateId(duplicate)) if duplicate == second.id,
+        ));
+
+        // Responses can be returned in any order.
+        node.send(
+            &json!([
+                { "jsonrpc": "2.0", "result": "0x1", "id": requests[1].id.0 },
+                { "jsonrpc": "2.0", "result": "0x2a", "id": requests[0].id.0 },
+            ])
+            .to_string(),
+        );
+        let responses = batch.await.unwrap().unwrap();
+        assert_eq!(responses.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn batch_responses_do_not_complete_single_calls() {
+        let (client, mut node) = connection();
+        let client = Arc::new(client);
+        let requests = vec![
+            jsonrpc::Request::new(eth::BlockNumber, Empty).unwrap(),
+            jsonrpc::Request::new(eth::ChainId, Empty).unwrap(),
+        ];
+        let single = jsonrpc::Request::new(eth::GasPrice, Empty).unwrap();
+
+        let single = tokio::spawn({
+            let client = client.clone();
+            async move { client.roundtrip(single).await }
+        });
+        let single_request = node.request().await;
+        let batch = tokio::spawn({
+            let client = client.clone();
+            let requests = requests.clone();
+            async move { client.roundtrip_batch(requests).await }
+        });
+        node.request().await;
+
+        // A node that mixes up responses should not cause the batch response
+        // to be matched to the single call.
+        node.send(
+            &json!([
+                { "jsonrpc": "2.0", "result": "0x0", "id": single_request["id"] },
+                { "jsonrpc": "2.0", "result": "0x1", "id": requests[1].id.0 },
+                { "jsonrpc": "2.0", "result": "0x2a", "id": requests[0].id.0 },
+            ])
+            .to_string(),
+        );
+        assert_eq!(batch.await.unwrap().unwrap().len(), 3);
+
+        node.send(
+            &json!({ "jsonrpc": "2.0", "result": "0x1", "id": single_request["id"] }).to_string(),
+        );
+        let response = single.await.unwrap().unwrap();
+        assert_eq!(response.result::<eth::GasPrice>().unwrap().unwrap(), 1_u128);
+    }
+
+    #[tokio::test]
+    async fn fails_batch_on_error_without_id() {
+        let (client, mut node) = connection();
+        let requests = vec![
+            jsonrpc::Request::new(eth::BlockNumber, Empty).unwrap(),
+            jsonrpc::Request::new(eth::ChainId, Empty).unwrap(),
+        ];
+
+        let (result, ()) = tokio::join!(
+            client.roundtrip_batch(requests.clone()),
+            node.respond(
+                r#"{"jsonrpc":"2.0","error":{"code":-32600,"message":"batch too large"},"id":null}"#
+            ),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Rpc(jsonrpc::Error {
+                code: jsonrpc::ErrorCode::InvalidRequest,
+                ..
+            })),
+        ));
+
+        let (result, ()) = tokio::join!(
+            client.roundtrip_batch(requests),
+            node.respond(
+                r#"[
+                    {"jsonrpc":"2.0","error":{"code":-32600,"message":"invalid request"},"id":null},
+                    {"jsonrpc":"2.0","error":{"code":-32600,"message":"invalid request"},"id":null}
+                ]"#
+            ),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Rpc(jsonrpc::Error {
+                code: jsonrpc::ErrorCode::InvalidRequest,
+                ..
+            })),
+        ));
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_connection_drops() {
+        let (client, mut nodes) = connections(2);
+        let mut second = nodes.pop().unwrap();
+        let mut first = nodes.pop().unwrap();
+
+        let (response, _) = tokio::join!(
+            client.roundtrip(jsonrpc::Request::new(eth::BlockNumber, Empty).unwrap()),
+            first.reply(json!("0x2a")),
+        );
+        assert_eq!(
+            response
+                .unwrap()
+                .result::<eth::BlockNumber>()
+                .unwrap()
+                .unwrap(),
+            42,
+        );
+
+        let (response, ()) = tokio::join!(
+            client.roundtrip(jsonrpc::Request::new(eth::BlockNumber, Empty).unwrap()),
+            async {
+                first.request().await;
+                drop(first);
+            },
+        );
+        assert!(matches!(response, Err(Error::Closed)));
+
+        let (response, _) = tokio::join!(
+            client.roundtrip(jsonrpc::Request::new(eth::BlockNumber, Empty).unwrap()),
+            second.reply(json!("0x2b")),
+        );
+        assert_eq!(
+            response
+                .unwrap()
+                .result::<eth::BlockNumber>()
+                .unwrap()
+                .unwrap(),
+            43,
+        );
+    }
+
+    #[tokio::test]
+    async fn delivers_subscription_notifications() {
+        let (client, mut node) = connection();
+
+        let (subscription, request) = tokio::join!(
+            client.subscribe::<u64>(SubscriptionKind::NewHeads),
+            node.reply(json!("0x1")),
+        );
+        let mut subscription = subscription.unwrap();
+        assert_eq!(request["method"], json!("eth_subscribe"));
+        assert_eq!(request["params"], json!(["newHeads"]));
+        assert_eq!(subscription.id().clone().into_raw(), "0x1");
+
+        let notification = |subscription: &str, result: u64| {
+            json!({
+                "jsonrpc": "2.0",
+                "method": "eth_subscription",
+                "params": { "subscription": subscription, "result": result },
+            })
+            .to_string()
+        };
+        node.send(&notification("0x1", 1));
+        node.send(&notification("0x2", 2));
+        node.send(&notification("0x1", 3));
+        assert_eq!(subscription.next().await.unwrap().unwrap(), 1);
+        assert_eq!(subscription.next().await.unwrap().unwrap(), 3);
+
+        let (unsubscribed, request) =
+            tokio::join!(subscription.unsubscribe(), node.reply(json!(true)));
+        assert!(unsubscribed.unwrap());
+        assert_eq!(request["method"], json!("eth_unsubscribe"));
+        assert_eq!(request["params"], json!(["0x1"]));
+
+        // Notifications received after unsubscribing are no longer delivered,
+        // and the stream ends.
+        node.send(&notification("0x1", 4));
+        assert!(subscription.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn ends_subscriptions_when_connection_drops() {
+        let (client, mut node) = connection();
+
+        let (subscription, _) = tokio::join!(
+            client.subscribe::<u64>(SubscriptionKind::NewPendingTransactions),
+            node.reply(json!("0x1")),
+        );
+        let mut subscription = subscription.unwrap();
+
+        drop(node);
+        assert!(matches!(
+            subscription.next().await,
+            Some(Err(Error::Closed)),
+        ));
+        assert!(subscription.next().await.is_none());
+    }
+}
@@ -0,0 +1,266 @@
+//! Failover JSON RPC client.
+//!
+//! This module provides a client over multiple endpoints that sends calls to
+//! the primary endpoint, and fails over to the next endpoint in case of
+//! transport errors (such as connection or HTTP failures). Endpoint health is
+//! tracked with a circuit breaker, so that endpoints that repeatedly fail are
+//! skipped for some time instead of being retried on every call.
+
+use crate::{
+    jsonrpc::{Request, Response},
+    transport::{BlockingTransport, Transport},
+};
+use std::{
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// A JSON RPC client that fails over across multiple endpoints.
+///
+/// Note that only failed roundtrips cause a failover. JSON RPC error responses
+/// (such as reverts) are returned to the caller as is.
+pub struct Failover<T> {
+    endpoints: Vec<T>,
+    health: Mutex<Vec<Health>>,
+    config: Configuration,
+}
+
+impl<T> Failover<T> {
+    /// Creates a new failover client for the specified endpoints, in order of
+    /// priority.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if no endpoints are specified.
+    pub fn new(endpoints: impl IntoIterator<Item = T>, config: Configuration) -> Self {
+        let endpoints = endpoints.into_iter().collect::<Vec<_>>();
+        assert!(
+            !endpoints.is_empty(),
+            "failover client requires at least one endpoint"
+        );
+        let health = Mutex::new(endpoints.iter().map(|_| Health::default()).collect());
+        Self {
+            endpoints,
+            health,
+            config,
+        }
+    }
+
+    /// Returns the endpoints, in order of priority.
+    pub fn endpoints(&self) -> &[T] {
+        &self.endpoints
+    }
+
+    /// Returns the order in which to try endpoints. Endpoints with a closed
+    /// circuit are tried in order of priority, followed by endpoints with an
+    /// open circuit as a last resort.
+    fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let health = self.health.lock().unwrap();
+        let (mut order, tripped): (Vec<_>, Vec<_>) =
+            (0..self.endpoints.len()).partition(|&i| !health[i].is_open(now));
+        order.extend(tripped);
+        order
+    }
+
+    /// Records the outcome of a roundtrip to an endpoint.
+    fn record(&self, index: usize, success: bool) {
+        let mut health = self.health.lock().unwrap();
+        let health = &mut health[index];
+        if success {
+            *health = Health::default();
+        } else {
+            health.failures += 1;
+            if health.failures >= self.config.failure_threshold {
+                health.open_until = Some(Instant::now() + self.config.cooldown);
+            }
+        }
+    }
+
+    async fn failover<'a, R, E, F, Fut>(&'a self, mut roundtrip: F) -> Result<R, E>
+    where
+        F: FnMut(&'a T) -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+    {
+        let mut last = None;
+        for index in self.order() {
+            let result = roundtrip(&self.endpoints[index]).await;
+            self.record(index, result.is_ok());
+            match result {
+                Ok(result) => return Ok(result),
+                Err(err) => last = Some(err),
+            }
+        }
+        Err(last.expect("failover client without endpoints"))
+    }
+
+    fn failover_blocking<R, E, F>(&self, mut roundtrip: F) -> Result<R, E>
+    where
+        F: FnMut(&T) -> Result<R, E>,
+    {
+        let mut last = None;
+        for index in self.order() {
+            let result = roundtrip(&self.endpoints[index]);
+            self.record(index, result.is_ok());
+            match result {
+                Ok(result) => return Ok(result),
+                Err(err) => last = Some(err),
+            }
+        }
+        Err(last.expect("failover client without endpoints"))
+    }
+}
+
+impl<T> Transport for Failover<T>
+where
+    T: Transport,
+{
+    type Error = T::Error;
+
+    async fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        self.failover(|endpoint| endpoint.roundtrip(request.clone()))
+            .await
+    }
+
+    async fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        self.failover(|endpoint| endpoint.roundtrip_batch(requests.clone()))
+            .await
+    }
+}
+
+impl<T> BlockingTransport for Failover<T>
+where
+    T: BlockingTransport,
+{
+    type Error = T::Error;
+
+    fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        self.failover_blocking(|endpoint| endpoint.roundtrip(request.clone()))
+    }
+
+    fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        self.failover_blocking(|endpoint| endpoint.roundtrip_batch(requests.clone()))
+    }
+}
+
+/// Failover configuration.
+#[derive(Clone, Debug)]
+pub struct Configuration {
+    /// The number of consecutive failures after which an endpoint's circuit is
+    /// opened.
+    pub failure_threshold: u32,
+    /// The amount of time an endpoint with an open circuit is skipped for.
+    /// After this time, the next call is allowed through to the endpoint to
+    /// check whether or not it recovered.
+    pub cooldown: Duration,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Endpoint health for circuit breaking.
+#[derive(Default)]
+struct Health {
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl Health {
+    fn is_open(&self, now: Instant) -> bool {
+        self.open_until.is_some_and(|until| now < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth,
+        mock::{self, Mock},
+        transport::BlockingTransportExt as _,
+        types::Empty,
+    };
+
+    /// An endpoint that returns its block number when up.
+    fn endpoint(block: u64, up: bool) -> Mock {
+        let mut mock = Mock::new();
+        if up {
+            mock.expect(eth::BlockNumber).returns(block);
+        } else {
+            mock.expect(eth::BlockNumber).fails("endpoint down");
+        }
+        mock
+    }
+
+    #[test]
+    fn fails_over_to_next_endpoint() {
+        let client = Failover::new(
+            [endpoint(1, false), endpoint(2, true)],
+            Configuration::default(),
+        );
+        assert_eq!(client.call(eth::BlockNumber, Empty).unwrap(), 2);
+        assert_eq!(
+            client
+                .batch([(eth::BlockNumber, Empty), (eth::BlockNumber, Empty)])
+                .unwrap(),
+            [2, 2],
+        );
+    }
+
+    #[test]
+    fn returns_last_error_when_all_endpoints_fail() {
+        let client = Failover::new(
+            [endpoint(1, false), endpoint(2, false)],
+            Configuration::default(),
+        );
+        assert!(matches!(
+            client.call_np(eth::BlockNumber),
+            Err(mock::Error::Failure(_)),
+        ));
+    }
+
+    #[test]
+    fn circuit_breaking() {
+        // The primary endpoint is down for the first two calls, and the
+        // secondary endpoint goes down after five calls.
+        let mut primary = Mock::new();
+        primary
+            .expect(eth::BlockNumber)
+            .fails("endpoint down")
+            .times(2);
+        primary.expect(eth::BlockNumber).returns(1);
+        let mut secondary = Mock::new();
+        secondary.expect(eth::BlockNumber).returns(2).times(5);
+        secondary.expect(eth::BlockNumber).fails("endpoint down");
+
+        let client = Failover::new(
+            [primary, secondary],
+            Configuration {
+                failure_threshold: 2,
+                cooldown: Duration::from_secs(60),
+            },
+        );
+        let [primary, secondary] = client.endpoints() else {
+            unreachable!()
+        };
+
+        for _ in 0..5 {
+            assert_eq!(client.call_np(eth::BlockNumber).unwrap(), 2);
+        }
+        assert_eq!(primary.requests().len(), 2);
+        assert_eq!(secondary.requests().len(), 5);
+
+        // Once the circuit is open, the endpoint is only used as a last resort.
+        assert_eq!(client.call_np(eth::BlockNumber).unwrap(), 1);
+        assert_eq!(client.call_np(eth::BlockNumber).unwrap(), 1);
+        assert_eq!(primary.requests().len(), 4);
+        assert_eq!(secondary.requests().len(), 6);
+    }
+}
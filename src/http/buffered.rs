@@ -4,8 +4,8 @@
 //! single JSON RPC batch call.
 
 use super::client::{Client, Error};
-use crate::{jsonrpc, method::Method, types::Empty};
-use futures::StreamExt as _;
+use crate::{instrumentation::Span, jsonrpc, method::Method, transport::Transport, types::Empty};
+use futures::{StreamExt as _, future};
 use serde::Serialize;
 use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 use tokio::sync::{mpsc, oneshot};
@@ -20,6 +20,7 @@ pub struct Buffered {
 struct Call {
     request: jsonrpc::Request,
     response: oneshot::Sender<Result<jsonrpc::Response, Error>>,
+    span: Span,
 }
 
 impl Client {
@@ -56,7 +57,15 @@ impl Buffered {
                 config.max_concurrent_requests.map(NonZeroUsize::get),
                 |mut chunk| {
                     let client = client.clone();
-                    async move {
+                    let span = Span::buffer(chunk.iter().map(|call| &call.span));
+                    if let Some(metrics) = client.metrics().filter(|_| !chunk.is_empty()) {
+                        metrics.coalesced(chunk.len());
+                    }
+                    let worker = async move {
+                        // Make sure to use the inherent `Client::roundtrip`
+                        // method and not the `Transport` implementation for
+                        // `Arc<Client>`.
+                        let client = &*client;
                         match chunk.len() {
                             0 => (),
                             1 => {
@@ -70,7 +79,7 @@ impl Buffered {
                                     .map(|call| (call.request, call.response))
                                     .unzip();
                                 let responses = client
-                                    .roundtrip::<_, Vec<jsonrpc::Response>>(requests)
+                                    .roundtrip_batch(requests)
                                     .await
                                     .map(|responses| {
                                         responses.into_iter().map(Ok).collect::<Vec<_>>()
@@ -83,7 +92,8 @@ impl Buffered {
                                 }
                             }
                         }
-                    }
+                    };
+                    async move { span.instrument(worker).await }
                 },
             )
             .await;
@@ -92,10 +102,12 @@ impl Buffered {
     async fn roundtrip(&self, request: jsonrpc::Request) -> Result<jsonrpc::Response, Error> {
         async {
             let (sender, receiver) = oneshot::channel();
+            let span = Span::queued(&request);
             self.calls
                 .send(Call {
                     request,
                     response: sender,
+                    span,
                 })
                 .ok()?;
             receiver.await.ok()
@@ -105,6 +117,10 @@ impl Buffered {
     }
 
     /// Executes a JSON RPC call.
+    ///
+    /// This is equivalent to [`TransportExt::call`].
+    ///
+    /// [`TransportExt::call`]: crate::transport::TransportExt::call
     pub async fn call<M>(&self, method: M, params: M::Params) -> Result<M::Result, Error>
     where
         M: Method + Serialize,
@@ -113,6 +129,10 @@ impl Buffered {
     }
 
     /// Executes a JSON RPC call with no parameters.
+    ///
+    /// This is equivalent to [`TransportExt::call_np`].
+    ///
+    /// [`TransportExt::call_np`]: crate::transport::TransportExt::call_np
     pub async fn call_np<M>(&self, method: M) -> Result<M::Result, Error>
     where
         M: Method<Params = Empty> + Serialize,
@@ -121,6 +141,31 @@ impl Buffered {
     }
 }
 
+impl Transport for Buffered {
+    type Error = Error;
+
+    async fn roundtrip(&self, request: jsonrpc::Request) -> Result<jsonrpc::Response, Error> {
+        Buffered::roundtrip(self, request).await
+    }
+
+    /// Sends a batch of JSON RPC requests.
+    ///
+    /// Note that each request in the batch is buffered individually, meaning
+    /// that they may be split across or combined with other concurrent calls
+    /// into different JSON RPC batches.
+    async fn roundtrip_batch(
+        &self,
+        requests: Vec<jsonrpc::Request>,
+    ) -> Result<Vec<jsonrpc::Response>, Error> {
+        future::try_join_all(
+            requests
+                .into_iter()
+                .map(|request| Buffered::roundtrip(self, request)),
+        )
+        .await
+    }
+}
+
 /// Buffered JSON RPC configuration.
 pub struct Configuration {
     /// The maximum amount of concurrent batches to send to the node.
@@ -1,22 +1,30 @@
 //! Ethereum JSON RPC HTTP client.
 
 use crate::{
+    instrumentation::{Span, Tracer},
     jsonrpc::{
         self, JsonError,
         batch::{self, Batch},
     },
     method::Method,
+    metrics::{Metrics, Probe},
+    transport::Transport,
     types::Empty,
 };
-use reqwest::{StatusCode, Url};
+use reqwest::{StatusCode, Url, header};
 use serde::{Serialize, de::DeserializeOwned};
 use std::{env, sync::Arc};
 use thiserror::Error;
 
 /// An Ethereum JSON RPC HTTP client.
+#[derive(Clone)]
 pub struct Client {
     client: reqwest::Client,
     url: Url,
+    metrics: Option<Arc<dyn Metrics>>,
+    tracer: Tracer,
+    #[cfg(feature = "jwt")]
+    jwt: Option<Arc<super::Jwt>>,
 }
 
 impl Client {
@@ -29,7 +37,40 @@ impl Client {
     /// Creates a new JSON RPC HTTP client for the specified client instance and
     /// URL.
     pub fn with_client(client: reqwest::Client, url: Url) -> Self {
-        Self { client, url }
+        Self {
+            client,
+            url,
+            metrics: None,
+            tracer: Tracer::default(),
+            #[cfg(feature = "jwt")]
+            jwt: None,
+        }
+    }
+
+    /// Configures the client to authenticate all requests with JWT bearer
+    /// tokens, as required by the Engine API.
+    #[cfg(feature = "jwt")]
+    pub fn with_jwt(mut self, jwt: super::Jwt) -> Self {
+        self.jwt = Some(Arc::new(jwt));
+        self
+    }
+
+    /// Configures the client to report metrics for all calls.
+    pub fn with_metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Configures which methods have their request and response bodies
+    /// redacted from tracing events.
+    #[cfg(feature = "tracing")]
+    pub fn with_redaction(mut self, redaction: crate::Redaction) -> Self {
+        self.tracer = Tracer::new(redaction);
+        self
+    }
+
+    pub(super) fn metrics(&self) -> Option<&dyn Metrics> {
+        self.metrics.as_deref()
     }
 
     /// Creates a new JSON RPC HTTP client from the environment. This method
@@ -48,28 +89,68 @@ impl Client {
         )
     }
 
-    pub(super) async fn roundtrip<T, R>(&self, request: T) -> Result<R, Error>
+    pub(super) async fn roundtrip(
+        &self,
+        request: jsonrpc::Request,
+    ) -> Result<jsonrpc::Response, Error> {
+        let span = self.tracer.call(&request);
+        let probe = Probe::start(self.metrics());
+        let response = span.instrument(self.send(&request, &span, &probe)).await;
+        span.finish_call(&response);
+        probe.finish_call(&request, &response);
+        response
+    }
+
+    pub(super) async fn roundtrip_batch(
+        &self,
+        requests: Vec<jsonrpc::Request>,
+    ) -> Result<Vec<jsonrpc::Response>, Error> {
+        let span = self.tracer.batch(&requests);
+        let probe = Probe::start(self.metrics());
+        let responses = span.instrument(self.send(&requests, &span, &probe)).await;
+        span.finish_batch(&requests, &responses);
+        probe.finish_batch(&requests, &responses);
+        responses
+    }
+
+    async fn send<T, R>(&self, request: &T, span: &Span, probe: &Probe<'_>) -> Result<R, Error>
     where
         T: Serialize,
         R: DeserializeOwned,
     {
-        let response = self
+        let body = serde_json::to_vec(request).map_err(JsonError::from)?;
+        probe.sent(body.len());
+        let request = self
             .client
             .post(self.url.clone())
-            .json(&request)
-            .send()
-            .await?;
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body);
+        #[cfg(feature = "jwt")]
+        let request = match &self.jwt {
+            Some(jwt) => request.bearer_auth(jwt.token()),
+            None => request,
+        };
+        let response = request.send().await?;
 
         let status = response.status();
+        span.status(status.as_u16().into());
+        probe.status(status.as_u16().into());
         if !status.is_success() {
-            return Err(Error::Status(status, response.text().await?));
+            let text = response.text().await?;
+            probe.received(text.len());
+            return Err(Error::Status(status, text));
         }
 
-        let body = response.json().await?;
-        Ok(body)
+        let body = response.bytes().await?;
+        probe.received(body.len());
+        Ok(serde_json::from_slice(&body).map_err(JsonError::from)?)
     }
 
     /// Executes a JSON RPC call.
+    ///
+    /// This is equivalent to [`TransportExt::call`].
+    ///
+    /// [`TransportExt::call`]: crate::transport::TransportExt::call
     pub async fn call<M>(&self, method: M, params: M::Params) -> Result<M::Result, Error>
     where
         M: Method + Serialize,
@@ -78,6 +159,10 @@ impl Client {
     }
 
     /// Executes a JSON RPC call with no parameters.
+    ///
+    /// This is equivalent to [`TransportExt::call_np`].
+    ///
+    /// [`TransportExt::call_np`]: crate::transport::TransportExt::call_np
     pub async fn call_np<M>(&self, method: M) -> Result<M::Result, Error>
     where
         M: Method<Params = Empty> + Serialize,
@@ -86,21 +171,44 @@ impl Client {
     }
 
     /// Executes a JSON RPC batch request.
+    ///
+    /// This is equivalent to [`TransportExt::batch`].
+    ///
+    /// [`TransportExt::batch`]: crate::transport::TransportExt::batch
     pub async fn batch<B>(&self, batch: B) -> Result<B::Values, Error>
     where
         B: Batch,
     {
-        batch::call_async(batch, |requests| self.roundtrip(requests)).await
+        batch::call_async(batch, |requests| self.roundtrip_batch(requests)).await
     }
 
     /// Executes a JSON RPC batch request, returning individual JSON RPC results
     /// for each batched requests. This allows fine-grained error handling
     /// for individual methods.
+    ///
+    /// This is equivalent to [`TransportExt::try_batch`].
+    ///
+    /// [`TransportExt::try_batch`]: crate::transport::TransportExt::try_batch
     pub async fn try_batch<B>(&self, batch: B) -> Result<B::Results, Error>
     where
         B: Batch,
     {
-        batch::try_call_async(batch, |requests| self.roundtrip(requests)).await
+        batch::try_call_async(batch, |requests| self.roundtrip_batch(requests)).await
+    }
+}
+
+impl Transport for Client {
+    type Error = Error;
+
+    async fn roundtrip(&self, request: jsonrpc::Request) -> Result<jsonrpc::Response, Error> {
+        Client::roundtrip(self, request).await
+    }
+
+    async fn roundtrip_batch(
+        &self,
+        requests: Vec<jsonrpc::Request>,
+    ) -> Result<Vec<jsonrpc::Response>, Error> {
+        Client::roundtrip_batch(self, requests).await
     }
 }
 
@@ -0,0 +1,143 @@
+//! JWT authentication for the Engine API.
+//!
+//! The authenticated Engine API port of an execution client requires each
+//! request to include an HS256 JWT bearer token with an `iat` (issued at)
+//! claim that is within 60 seconds of the node's current time. See
+//! <https://github.com/ethereum/execution-apis/blob/main/src/engine/authentication.md>.
+
+use crate::serialization::bytes;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac as _};
+use serde::de;
+use sha2::Sha256;
+use std::{
+    fmt::{self, Debug, Formatter},
+    fs, io,
+    path::Path,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+use thiserror::Error;
+
+/// The amount of time a token is reused for before a new one is issued. This
+/// is well within the 60 second window accepted by nodes.
+const REFRESH: Duration = Duration::from_secs(30);
+
+/// A JWT secret used for generating bearer tokens for authenticated Engine API
+/// requests.
+pub struct Jwt {
+    secret: [u8; 32],
+    token: Mutex<Option<(String, SystemTime)>>,
+}
+
+impl Jwt {
+    /// Creates a new JWT authenticator with the specified 32-byte secret.
+    pub fn new(secret: [u8; 32]) -> Self {
+        Self {
+            secret,
+            token: Mutex::default(),
+        }
+    }
+
+    /// Creates a new JWT authenticator from a hex-encoded secret, with or
+    /// without a `0x` prefix.
+    pub fn from_hex(secret: &str) -> Result<Self, Error> {
+        let secret = secret.trim();
+        let hex = secret.strip_prefix("0x").unwrap_or(secret);
+        if hex.len() != 64 {
+            return Err(Error::InvalidSecret);
+        }
+
+        let mut bytes = [0; 32];
+        bytes::decode_into::<de::value::Error>(&format!("0x{hex}"), &mut bytes)
+            .map_err(|_| Error::InvalidSecret)?;
+        Ok(Self::new(bytes))
+    }
+
+    /// Creates a new JWT authenticator from a file containing a hex-encoded
+    /// secret, such as the `jwt.hex` file used by execution clients.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::from_hex(&fs::read_to_string(path)?)
+    }
+
+    /// Returns a bearer token for authenticating a request.
+    ///
+    /// Tokens are cached and automatically refreshed before they expire.
+    pub fn token(&self) -> String {
+        let now = SystemTime::now();
+        let mut token = self.token.lock().unwrap();
+        match &*token {
+            Some((token, issued))
+                if now
+                    .duration_since(*issued)
+                    .is_ok_and(|elapsed| elapsed < REFRESH) =>
+            {
+                token.clone()
+            }
+            _ => {
+                let new = self.issue(now);
+                *token = Some((new.clone(), now));
+                new
+            }
+        }
+    }
+
+    /// Issues a new token at the specified time.
+    fn issue(&self, now: SystemTime) -> String {
+        let iat = now
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(format!(r#"{{"iat":{iat}}}"#));
+        let message = format!("{header}.{claims}");
+
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update(message.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+
+        format!("{message}.{signature}")
+    }
+}
+
+impl Debug for Jwt {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Jwt").finish_non_exhaustive()
+    }
+}
+
+/// An error configuring a JWT secret.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to read JWT secret: {0}")]
+    Io(#[from] io::Error),
+    #[error("JWT secret must be 32 hex-encoded bytes")]
+    InvalidSecret,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hs256_token() {
+        let jwt =
+            Jwt::from_hex("0x7365637265747365637265747365637265747365637265747365637265747365")
+                .unwrap();
+        assert_eq!(
+            jwt.issue(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.\
+             eyJpYXQiOjE3MDAwMDAwMDB9.\
+             M2nY84VaM-AUfRGDPC_vdyUsN1rvFs8UaGBU76h2vK4",
+        );
+        assert_eq!(jwt.token(), jwt.token());
+    }
+
+    #[test]
+    fn invalid_secrets() {
+        assert!(Jwt::from_hex("0x1234").is_err());
+        assert!(Jwt::from_hex(&"zz".repeat(32)).is_err());
+        assert!(Jwt::from_hex(&format!("{}\n", "ab".repeat(32))).is_ok());
+    }
+}
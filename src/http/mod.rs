@@ -1,6 +1,10 @@
 pub mod buffered;
 mod client;
+#[cfg(feature = "jwt")]
+pub mod jwt;
 
+#[cfg(feature = "jwt")]
+pub use self::jwt::Jwt;
 pub use self::{
     buffered::Buffered,
     client::{Client, Error},
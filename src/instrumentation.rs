@@ -0,0 +1,390 @@
+//! Tracing instrumentation for JSON RPC clients.
+//!
+//! When the `tracing` feature is enabled, clients emit an `ethrpc.call` span
+//! for each JSON RPC call and an `ethrpc.batch` span for each batch, recording
+//! the method names, request IDs, batch sizes, latencies, HTTP status codes and
+//! JSON RPC error codes. The buffered HTTP client additionally emits an
+//! `ethrpc.queued` span for each call while it is waiting to be sent, and an
+//! `ethrpc.buffer` span that follows from all the calls that were coalesced
+//! into a single request.
+//!
+//! Request and response bodies are emitted as `DEBUG` events. Bodies for
+//! sensitive methods (such as `eth_sign`) are redacted, which can be configured
+//! per client with a [`Redaction`].
+//!
+//! Without the `tracing` feature, the instrumentation compiles down to nothing.
+
+use crate::jsonrpc::{Request, Response};
+use std::fmt::Display;
+#[cfg(feature = "tracing")]
+use std::sync::Arc;
+
+/// Client instrumentation configuration.
+#[derive(Clone, Default)]
+pub(crate) struct Tracer {
+    #[cfg(feature = "tracing")]
+    redaction: Redaction,
+}
+
+#[cfg(feature = "tracing")]
+impl Tracer {
+    /// Creates a new tracer with the specified redaction.
+    pub(crate) fn new(redaction: Redaction) -> Self {
+        Self { redaction }
+    }
+
+    /// Creates a new span for a JSON RPC call.
+    pub(crate) fn call(&self, request: &Request) -> Span {
+        let span = tracing::info_span!(
+            "ethrpc.call",
+            method = request.method.as_str(),
+            id = request.id.0,
+            status = tracing::field::Empty,
+            error_code = tracing::field::Empty,
+            latency = tracing::field::Empty,
+        );
+        span.in_scope(
+            || tracing::debug!(request = %body(&self.redaction, request), "sending request"),
+        );
+        Span::new(
+            span,
+            self.redaction.clone(),
+            self.redaction.is_redacted(request.method.as_str()),
+        )
+    }
+
+    /// Creates a new span for a JSON RPC batch.
+    pub(crate) fn batch(&self, requests: &[Request]) -> Span {
+        let span = tracing::info_span!(
+            "ethrpc.batch",
+            size = requests.len(),
+            methods = ?methods(requests),
+            ids = ?ids(requests),
+            status = tracing::field::Empty,
+            errors = tracing::field::Empty,
+            latency = tracing::field::Empty,
+        );
+        span.in_scope(
+            || tracing::debug!(requests = %bodies(&self.redaction, requests), "sending batch"),
+        );
+        Span::new(span, self.redaction.clone(), false)
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl Tracer {
+    pub(crate) fn call(&self, _: &Request) -> Span {
+        Span {}
+    }
+
+    pub(crate) fn batch(&self, _: &[Request]) -> Span {
+        Span {}
+    }
+}
+
+/// An instrumentation span for a JSON RPC call or batch.
+pub(crate) struct Span {
+    #[cfg(feature = "tracing")]
+    inner: tracing::Span,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+    #[cfg(feature = "tracing")]
+    redaction: Redaction,
+    #[cfg(feature = "tracing")]
+    redacted: bool,
+}
+
+#[cfg(feature = "tracing")]
+impl Span {
+    fn new(inner: tracing::Span, redaction: Redaction, redacted: bool) -> Self {
+        Self {
+            inner,
+            start: std::time::Instant::now(),
+            redaction,
+            redacted,
+        }
+    }
+
+    /// Creates a new span for a call that is queued to be sent with other
+    /// calls.
+    #[cfg(feature = "http")]
+    pub(crate) fn queued(request: &Request) -> Self {
+        Self::new(
+            tracing::info_span!(
+                "ethrpc.queued",
+                method = request.method.as_str(),
+                id = request.id.0,
+            ),
+            Redaction::default(),
+            false,
+        )
+    }
+
+    /// Creates a new span for sending queued calls, which follows from the
+    /// spans of each of the calls.
+    #[cfg(feature = "http")]
+    pub(crate) fn buffer<'a>(queued: impl IntoIterator<Item = &'a Self>) -> Self {
+        let span = tracing::info_span!("ethrpc.buffer", size = tracing::field::Empty);
+        let mut size = 0;
+        for call in queued {
+            span.follows_from(&call.inner);
+            size += 1;
+        }
+        span.record("size", size);
+        Self::new(span, Redaction::default(), false)
+    }
+
+    /// Instruments a future with this span.
+    #[cfg(feature = "http")]
+    pub(crate) fn instrument<F>(&self, future: F) -> impl Future<Output = F::Output>
+    where
+        F: Future,
+    {
+        tracing::Instrument::instrument(future, self.inner.clone())
+    }
+
+    /// Runs a closure within this span.
+    pub(crate) fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.inner.in_scope(f)
+    }
+
+    /// Records the HTTP status code of the response.
+    pub(crate) fn status(&self, status: u32) {
+        self.inner.record("status", status);
+    }
+
+    /// Records the result of a JSON RPC call.
+    pub(crate) fn finish_call<E>(&self, result: &Result<Response, E>)
+    where
+        E: Display,
+    {
+        self.inner
+            .record("latency", tracing::field::debug(self.start.elapsed()));
+        self.in_scope(|| match result {
+            Ok(response) => {
+                if let Err(err) = &response.result {
+                    self.inner.record("error_code", i32::from(err.code));
+                }
+                let response = if self.redacted {
+                    REDACTED.to_owned()
+                } else {
+                    serde_json::to_string(response).unwrap_or_default()
+                };
+                tracing::debug!(%response, "received response");
+            }
+            Err(err) => tracing::warn!(error = %err, "request failed"),
+        });
+    }
+
+    /// Records the result of a JSON RPC batch.
+    pub(crate) fn finish_batch<E>(&self, requests: &[Request], result: &Result<Vec<Response>, E>)
+    where
+        E: Display,
+    {
+        self.inner
+            .record("latency", tracing::field::debug(self.start.elapsed()));
+        self.in_scope(|| match result {
+            Ok(responses) => {
+                let errors = responses
+                    .iter()
+                    .filter_map(|response| Some(i32::from(response.result.as_ref().err()?.code)))
+                    .collect::<Vec<_>>();
+                if !errors.is_empty() {
+                    self.inner.record("errors", tracing::field::debug(errors));
+                }
+                tracing::debug!(
+                    responses = %batch_responses(&self.redaction, requests, responses),
+                    "received batch responses",
+                );
+            }
+            Err(err) => tracing::warn!(error = %err, "batch request failed"),
+        });
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl Span {
+    #[cfg(feature = "http")]
+    pub(crate) fn queued(_: &Request) -> Self {
+        Self {}
+    }
+
+    #[cfg(feature = "http")]
+    pub(crate) fn buffer<'a>(_: impl IntoIterator<Item = &'a Self>) -> Self {
+        Self {}
+    }
+
+    #[cfg(feature = "http")]
+    pub(crate) fn instrument<F>(&self, future: F) -> impl Future<Output = F::Output>
+    where
+        F: Future,
+    {
+        future
+    }
+
+    #[cfg(feature = "curl")]
+    pub(crate) fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        f()
+    }
+
+    pub(crate) fn status(&self, _: u32) {}
+
+    pub(crate) fn finish_call<E>(&self, _: &Result<Response, E>)
+    where
+        E: Display,
+    {
+    }
+
+    pub(crate) fn finish_batch<E>(&self, _: &[Request], _: &Result<Vec<Response>, E>)
+    where
+        E: Display,
+    {
+    }
+}
+
+/// Configures which methods have their request and response bodies redacted
+/// from `DEBUG` events.
+///
+/// By default, bodies for signing methods (`eth_sign`, `eth_signTransaction`,
+/// `eth_signTypedData*` and `personal_*`) as well as `engine_*` methods are
+/// redacted.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Default)]
+pub struct Redaction(Option<Arc<RedactionFn>>);
+
+/// A function that determines whether or not bodies for a method should be
+/// redacted.
+#[cfg(feature = "tracing")]
+type RedactionFn = dyn Fn(&str) -> bool + Send + Sync;
+
+#[cfg(feature = "tracing")]
+impl Redaction {
+    /// Creates a new redaction from a function that determines whether or not
+    /// bodies for a method should be redacted.
+    pub fn new<F>(redaction: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        Self(Some(Arc::new(redaction)))
+    }
+
+    /// Returns whether or not bodies for the specified method are redacted.
+    pub fn is_redacted(&self, method: &str) -> bool {
+        match &self.0 {
+            Some(redaction) => redaction(method),
+            None => {
+                matches!(method, "eth_sign" | "eth_signTransaction")
+                    || method.starts_with("eth_signTypedData")
+                    || method.starts_with("personal_")
+                    || method.starts_with("engine_")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+const REDACTED: &str = "<redacted>";
+
+/// Returns the method names of a batch of requests.
+#[cfg(feature = "tracing")]
+fn methods(requests: &[Request]) -> Vec<&str> {
+    requests
+        .iter()
+        .map(|request| request.method.as_str())
+        .collect()
+}
+
+/// Returns the request IDs of a batch of requests.
+#[cfg(feature = "tracing")]
+fn ids(requests: &[Request]) -> Vec<u32> {
+    requests.iter().map(|request| request.id.0).collect()
+}
+
+/// Returns the request body for logging.
+#[cfg(feature = "tracing")]
+fn body(redaction: &Redaction, request: &Request) -> String {
+    if redaction.is_redacted(request.method.as_str()) {
+        return REDACTED.to_owned();
+    }
+    serde_json::to_string(request).unwrap_or_default()
+}
+
+/// Returns the request bodies of a batch for logging.
+#[cfg(feature = "tracing")]
+fn bodies(redaction: &Redaction, requests: &[Request]) -> String {
+    let bodies = requests
+        .iter()
+        .map(|request| body(redaction, request))
+        .collect::<Vec<_>>();
+    format!("[{}]", bodies.join(","))
+}
+
+/// Returns the response bodies of a batch for logging, redacting responses to
+/// sensitive methods.
+#[cfg(feature = "tracing")]
+fn batch_responses(redaction: &Redaction, requests: &[Request], responses: &[Response]) -> String {
+    let bodies = responses
+        .iter()
+        .map(|response| {
+            let redacted = requests
+                .iter()
+                .find(|request| Some(request.id) == response.id)
+                .is_some_and(|request| redaction.is_redacted(request.method.as_str()));
+            if redacted {
+                REDACTED.to_owned()
+            } else {
+                serde_json::to_string(response).unwrap_or_default()
+            }
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", bodies.join(","))
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+    use crate::{eth, jsonrpc, types::Empty};
+    use ethprim::address;
+
+    #[test]
+    fn redacts_sensitive_bodies() {
+        let redaction = Redaction::default();
+        assert!(redaction.is_redacted("eth_sign"));
+        assert!(redaction.is_redacted("eth_signTypedData_v4"));
+        assert!(!redaction.is_redacted("eth_blockNumber"));
+
+        let custom = Redaction::new(|method| method == "eth_blockNumber");
+        assert!(custom.is_redacted("eth_blockNumber"));
+        assert!(!custom.is_redacted("eth_sign"));
+
+        let requests = [
+            Request::new(eth::BlockNumber, Empty).unwrap(),
+            Request::new(
+                eth::Sign,
+                (
+                    address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41"),
+                    b"secret".to_vec(),
+                ),
+            )
+            .unwrap(),
+        ];
+        let responses = requests
+            .iter()
+            .map(|request| Response {
+                jsonrpc: jsonrpc::Version::V2,
+                result: Ok(jsonrpc::Value(serde_json::json!("0x2a"))),
+                id: Some(request.id),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids(&requests), [requests[0].id.0, requests[1].id.0]);
+        assert!(!bodies(&redaction, &requests).contains("0x736563726574"));
+        assert_eq!(
+            batch_responses(&redaction, &requests, &responses),
+            format!(
+                r#"[{{"jsonrpc":"2.0","result":"0x2a","id":{}}},{REDACTED}]"#,
+                requests[0].id.0,
+            ),
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Ethereum JSON RPC IPC client.
+//!
+//! This client talks to a node over its Unix domain socket (such as a Geth or
+//! Reth `.ipc` endpoint). Concurrent JSON RPC calls are multiplexed over a
+//! single connection, with responses matched to their requests by JSON RPC ID.
+//! If the connection drops, it is re-established on the next call.
+
+use crate::{
+    duplex::{self, ClientError, Connection},
+    jsonrpc::{self, Id, JsonError, batch},
+    transport::{PubSubTransport, Transport},
+    types::SubscriptionKind,
+};
+use serde::de::DeserializeOwned;
+use std::{io, path::PathBuf};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    net::UnixStream,
+};
+
+/// An Ethereum JSON RPC IPC client.
+///
+/// Calls are executed with the [`TransportExt`](crate::transport::TransportExt)
+/// extension methods, and subscriptions are created with the
+/// [`PubSubTransport`] methods.
+pub struct Client {
+    inner: duplex::Client<Socket>,
+}
+
+/// A subscription to node events over an IPC connection.
+pub type Subscription<T> = duplex::Subscription<T, Error>;
+
+impl Client {
+    /// Creates a new JSON RPC IPC client for the specified socket path.
+    ///
+    /// The connection is established lazily on the first call. Note that this
+    /// spawns a background worker task that owns the connection, and must be
+    /// called from within a Tokio runtime.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner: duplex::Client::new(path.into()),
+        }
+    }
+}
+
+impl Transport for Client {
+    type Error = Error;
+
+    async fn roundtrip(&self, request: jsonrpc::Request) -> Result<jsonrpc::Response, Error> {
+        self.inner.roundtrip(request).await
+    }
+
+    async fn roundtrip_batch(
+        &self,
+        requests: Vec<jsonrpc::Request>,
+    ) -> Result<Vec<jsonrpc::Response>, Error> {
+        self.inner.roundtrip_batch(requests).await
+    }
+}
+
+impl PubSubTransport for Client {
+    async fn subscribe<T>(&self, kind: SubscriptionKind) -> Result<Subscription<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.inner.subscribe(kind).await
+    }
+}
+
+/// A Unix domain socket connection to a node.
+///
+/// Nodes write JSON values back-to-back on the socket, optionally delimited by
+/// newlines. Since messages may be split across reads, incoming data is
+/// buffered and scanned for complete top-level JSON values.
+struct Socket {
+    stream: UnixStream,
+    buffer: Vec<u8>,
+    scanner: Scanner,
+}
+
+impl Connection for Socket {
+    type Endpoint = PathBuf;
+    type Error = Error;
+
+    async fn connect(path: &PathBuf) -> Result<Self, Error> {
+        Ok(Self {
+            stream: UnixStream::connect(path).await?,
+            buffer: Vec::new(),
+            scanner: Scanner::default(),
+        })
+    }
+
+    async fn send(&mut self, mut message: String) -> Result<(), Error> {
+        message.push('\n');
+        self.stream.write_all(message.as_bytes()).await?;
+        Ok(())
+    }
+
+    async fn receive(&mut self) -> Option<Vec<u8>> {
+        loop {
+            if let Some(len) = self.scanner.scan(&self.buffer).ok()? {
+                self.scanner = Scanner::default();
+                return Some(self.buffer.drain(..len).collect());
+            }
+
+            // Note that reading is cancel safe, and all other state is kept
+            // in `self`, making this method cancel safe.
+            self.buffer.reserve(0x2000);
+            match self.stream.read_buf(&mut self.buffer).await {
+                Ok(0) | Err(_) => return None,
+                Ok(_) => (),
+            }
+        }
+    }
+
+    async fn close(mut self) {
+        let _ = self.stream.shutdown().await;
+    }
+}
+
+/// An incremental scanner for finding the end of a top-level JSON object or
+/// array in a stream of bytes.
+#[derive(Default)]
+struct Scanner {
+    offset: usize,
+    depth: usize,
+    string: bool,
+    escape: bool,
+}
+
+impl Scanner {
+    /// Scans the buffer for the end of the first complete JSON value,
+    /// returning its length including any leading whitespace. The scanner
+    /// remembers its progress, so that subsequent calls only scan newly
+    /// buffered data.
+    ///
+    /// Returns an error if the buffer contains something other than a JSON
+    /// object or array.
+    fn scan(&mut self, buffer: &[u8]) -> Result<Option<usize>, ()> {
+        while let Some(&byte) = buffer.get(self.offset) {
+            self.offset += 1;
+            if self.string {
+                match byte {
+                    _ if self.escape => self.escape = false,
+                    b'\\' => self.escape = true,
+                    b'"' => self.string = false,
+                    _ => (),
+                }
+                continue;
+            }
+            match byte {
+                b'"' if self.depth > 0 => self.string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' if self.depth > 0 => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        return Ok(Some(self.offset));
+                    }
+                }
+                _ if self.depth > 0 => (),
+                b' ' | b'\t' | b'\n' | b'\r' => (),
+                _ => return Err(()),
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// An error using an IPC client.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON error: {0}")]
+    Json(#[from] JsonError),
+    #[error("IPC error: {0}")]
+    Io(#[from] io::Error),
+    #[error("IPC connection closed")]
+    Closed,
+    #[error("request ID {} is already in use by another call", .0.0)]
+    DuplicateId(Id),
+    #[error(transparent)]
+    Rpc(#[from] jsonrpc::Error),
+    #[error(transparent)]
+    Batch(#[from] batch::Error),
+}
+
+impl ClientError for Error {
+    fn closed() -> Self {
+        Self::Closed
+    }
+
+    fn duplicate_id(id: Id) -> Self {
+        Self::DuplicateId(id)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(data: &[u8], chunk: usize) -> Vec<String> {
+        let mut buffer = Vec::new();
+        let mut scanner = Scanner::default();
+        let mut messages = Vec::new();
+        for bytes in data.chunks(chunk) {
+            buffer.extend_from_slice(bytes);
+            while let Some(len) = scanner.scan(&buffer).unwrap() {
+                scanner = Scanner::default();
+                let message = buffer.drain(..len).collect::<Vec<_>>();
+                messages.push(String::from_utf8(message).unwrap().trim().to_owned());
+            }
+        }
+        messages
+    }
+
+    #[test]
+    fn scans_stream_delimited_messages() {
+        let data =
+            br#"{"jsonrpc":"2.0","result":"0x1","id":1}[{"jsonrpc":"2.0","result":"}]\"{[","id":2}]
+            {"jsonrpc":"2.0","result":{"a":[1,2,{}]},"id":3}
+        "#;
+        for chunk in [1, 7, data.len()] {
+            assert_eq!(
+                messages(data, chunk),
+                [
+                    r#"{"jsonrpc":"2.0","result":"0x1","id":1}"#,
+                    r#"[{"jsonrpc":"2.0","result":"}]\"{[","id":2}]"#,
+                    r#"{"jsonrpc":"2.0","result":{"a":[1,2,{}]},"id":3}"#,
+                ],
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_container_messages() {
+        assert!(Scanner::default().scan(b" 42").is_err());
+    }
+}
@@ -46,7 +46,7 @@ where
 }
 
 /// JSON RPC supported version.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum Version {
     /// Version 2.0 of the JSON RPC specification.
     #[serde(rename = "2.0")]
@@ -64,7 +64,7 @@ pub enum Version {
 pub struct Id(pub u32);
 
 impl Id {
-    fn next() -> Self {
+    pub(crate) fn next() -> Self {
         static ID: AtomicU32 = AtomicU32::new(0);
         Self(ID.fetch_add(1, atomic::Ordering::Relaxed))
     }
@@ -93,7 +93,7 @@ impl Method {
 }
 
 /// A request object.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Request {
     pub jsonrpc: Version,
     pub method: Method,
@@ -117,7 +117,7 @@ impl Request {
 }
 
 /// Notification object.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Notification {
     pub jsonrpc: Version,
     pub method: Method,
@@ -139,7 +139,7 @@ impl Notification {
 }
 
 /// Response object.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Response {
     pub jsonrpc: Version,
     pub result: Result<Value, Error>,
@@ -216,7 +216,9 @@ impl<'de> Deserialize<'de> for Response {
                             if id.is_some() {
                                 return Err(de::Error::duplicate_field("id"));
                             }
-                            id = Some(map.next_value()?);
+                            // Note that error responses to requests that could
+                            // not be processed have a `null` ID.
+                            id = Some(map.next_value::<Option<Id>>()?);
                         }
                     }
                 }
@@ -236,7 +238,7 @@ impl<'de> Deserialize<'de> for Response {
                             return Err(de::Error::custom("missing 'result' or 'error' field"));
                         }
                     },
-                    id,
+                    id: id.flatten(),
                 })
             }
         }
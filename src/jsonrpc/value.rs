@@ -13,7 +13,7 @@ use std::{
 use thiserror::Error;
 
 /// An arbitrary JSON value.
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct Value(pub serde_json::Value);
 
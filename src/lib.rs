@@ -3,21 +3,50 @@
 //! Documentation for the APIs can be found here:
 //! <https://ethereum.github.io/execution-apis/>
 
+pub mod cache;
+pub mod cassette;
 #[cfg(feature = "curl")]
 pub mod curl;
+#[cfg(any(all(feature = "ipc", unix), feature = "ws"))]
+mod duplex;
 pub mod ext;
+pub mod failover;
 #[cfg(feature = "http")]
 pub mod http;
+#[cfg(any(feature = "curl", feature = "http"))]
+mod instrumentation;
+#[cfg(all(feature = "ipc", unix))]
+pub mod ipc;
 pub mod jsonrpc;
 #[macro_use]
 pub mod method;
 mod bloom;
-mod debug;
+mod hex;
+#[cfg(any(feature = "curl", feature = "http", feature = "metrics"))]
+pub mod metrics;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+#[cfg(feature = "quorum")]
+pub mod quorum;
+#[cfg(feature = "ratelimit")]
+pub mod ratelimit;
+#[cfg(feature = "retry")]
+pub mod retry;
 mod serialization;
+pub mod server;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "tower")]
+pub mod tower;
+pub mod transport;
 pub mod types;
+#[cfg(feature = "ws")]
+pub mod ws;
 
+#[cfg(any(all(feature = "ipc", unix), feature = "ws"))]
+pub use self::duplex::Subscription;
+#[cfg(all(feature = "tracing", any(feature = "curl", feature = "http")))]
+pub use self::instrumentation::Redaction;
 use self::types::*;
 
 module! {
@@ -70,6 +99,10 @@ module! {
         pub struct GasPrice as "eth_gasPrice"
             Empty => U256;
 
+        /// Returns the state of the account of given address.
+        pub struct GetAccount as "eth_getAccount"
+            (Address, BlockId) => Option<Account>;
+
         /// Returns the balance of the account of given address.
         pub struct GetBalance as "eth_getBalance"
             (Address, BlockId) => U256;
@@ -130,15 +163,27 @@ module! {
         pub struct GetStorageValues as "eth_getStorageValues"
             (StorageRequests, BlockId) => StorageValues;
 
+        /// Returns information about a block header by hash.
+        pub struct GetHeaderByHash as "eth_getHeaderByHash"
+            (Digest,) => Option<Header>;
+
+        /// Returns information about a block header by number.
+        pub struct GetHeaderByNumber as "eth_getHeaderByNumber"
+            (BlockSpec,) => Option<Header>;
+
+        /// Returns the RLP encoded transaction with the given hash.
+        pub struct GetRawTransactionByHash as "eth_getRawTransactionByHash"
+            (Digest,) => Option<Vec<u8>> [serialization::option_bytes];
+
         /// Returns information about a transaction by block hash and
         /// transaction index position.
         pub struct GetTransactionByBlockHashAndIndex as "eth_getTransactionByBlockHashAndIndex"
-            (Digest, u64) [serialization::param::get_transaction_by_block_and_index] => Option<SignedTransaction>;
+            (Digest, u64) [serialization::param::block_and_index] => Option<SignedTransaction>;
 
         /// Returns information about a transaction by block number and
         /// transaction index position.
         pub struct GetTransactionByBlockNumberAndIndex as "eth_getTransactionByBlockNumberAndIndex"
-            (BlockSpec, u64) [serialization::param::get_transaction_by_block_and_index] => Option<SignedTransaction>;
+            (BlockSpec, u64) [serialization::param::block_and_index] => Option<SignedTransaction>;
 
         /// Returns the information about a transaction requested by transaction
         /// hash.
@@ -158,10 +203,39 @@ module! {
         pub struct GetTransactionReceipt as "eth_getTransactionReceipt"
             (Digest,) => Option<TransactionReceipt>;
 
+        /// Returns information about an uncle of a block by block hash and
+        /// uncle index position.
+        pub struct GetUncleByBlockHashAndIndex as "eth_getUncleByBlockHashAndIndex"
+            (Digest, u64) [serialization::param::block_and_index] => Option<Header>;
+
+        /// Returns information about an uncle of a block by block number and
+        /// uncle index position.
+        pub struct GetUncleByBlockNumberAndIndex as "eth_getUncleByBlockNumberAndIndex"
+            (BlockSpec, u64) [serialization::param::block_and_index] => Option<Header>;
+
+        /// Returns the number of uncles in a block from a block matching the
+        /// given block hash.
+        pub struct GetUncleCountByBlockHash as "eth_getUncleCountByBlockHash"
+            (Digest,) => Option<u64> [serialization::option_num];
+
+        /// Returns the number of uncles in a block matching the given block
+        /// number.
+        pub struct GetUncleCountByBlockNumber as "eth_getUncleCountByBlockNumber"
+            (BlockSpec,) => Option<u64> [serialization::option_num];
+
+        /// Returns the number of hashes per second that the node is mining
+        /// with.
+        pub struct Hashrate as "eth_hashrate"
+            Empty => U256;
+
         /// Returns the current maxPriorityFeePerGas per gas in wei.
         pub struct MaxPriorityFeePerGas as "eth_maxPriorityFeePerGas"
             Empty => U256;
 
+        /// Returns whether or not the client is actively mining new blocks.
+        pub struct Mining as "eth_mining"
+            Empty => bool;
+
         /// Creates a filter in the node, allowing for later polling.
         /// Registers client interest in new blocks, and returns an identifier.
         pub struct NewBlockFilter as "eth_newBlockFilter"
@@ -179,9 +253,13 @@ module! {
         pub struct NewPendingTransactionFilter as "eth_newPendingTransactionFilter"
             Empty => FilterId;
 
+        /// Returns the current Ethereum protocol version.
+        pub struct ProtocolVersion as "eth_protocolVersion"
+            Empty => u64 [serialization::num];
+
         /// Submits a raw transaction.
         pub struct SendRawTransaction as "eth_sendRawTransaction"
-            (Vec<u8>,) [serialization::param::eth_send_raw_transaction] => Digest;
+            (Vec<u8>,) [serialization::param::bytes] => Digest;
 
         /// Signs and submits a transaction.
         pub struct SendTransaction as "eth_sendTransaction"
@@ -189,7 +267,7 @@ module! {
 
         /// Returns an EIP-191 signature over the provided data.
         pub struct Sign as "eth_sign"
-            (Address, Vec<u8>) [serialization::param::eth_sign] => Vec<u8> [serialization::bytes];
+            (Address, Vec<u8>) [serialization::param::address_bytes] => Vec<u8> [serialization::bytes];
 
         /// Returns an RLP encoded transaction signed by the specified account.
         pub struct SignTransaction as "eth_signTransaction"
@@ -201,6 +279,15 @@ module! {
         pub struct SimulateV1 as "eth_simulateV1"
             (SimulatePayload, Option<BlockSpec>) [serialization::param::call_like] => Vec<BlockResult>;
 
+        /// Creates a new subscription for the specified events. Notifications
+        /// for the subscription are sent to the client with the
+        /// `eth_subscription` method.
+        ///
+        /// Note that subscriptions are only supported by transports that
+        /// support server notifications, such as WebSockets.
+        pub struct Subscribe as "eth_subscribe"
+            SubscriptionKind => SubscriptionId;
+
         /// Returns an object with data about the sync status or false.
         pub struct Syncing as "eth_syncing"
             Empty => SyncingStatus;
@@ -208,12 +295,282 @@ module! {
         /// Uninstalls a filter with given id.
         pub struct UninstallFilter as "eth_uninstallFilter"
             (FilterId,) => bool;
+
+        /// Cancels a subscription with the given ID.
+        pub struct Unsubscribe as "eth_unsubscribe"
+            (SubscriptionId,) => bool;
+    }
+}
+
+module! {
+    /// The `admin` namespace for node operations.
+    pub mod admin {
+        /// Connects to a peer with the specified enode URL.
+        pub struct AddPeer as "admin_addPeer"
+            (String,) => bool;
+
+        /// Adds a peer with the specified enode URL as trusted, allowing it
+        /// to always connect even if the peer limit is reached.
+        pub struct AddTrustedPeer as "admin_addTrustedPeer"
+            (String,) => bool;
+
+        /// Returns the absolute path of the data directory of the node.
+        pub struct Datadir as "admin_datadir"
+            Empty => String;
+
+        /// Returns information about the running node.
+        pub struct NodeInfo as "admin_nodeInfo"
+            Empty => types::NodeInfo;
+
+        /// Returns information about the connected peers.
+        pub struct Peers as "admin_peers"
+            Empty => Vec<PeerInfo>;
+
+        /// Disconnects from a peer with the specified enode URL.
+        pub struct RemovePeer as "admin_removePeer"
+            (String,) => bool;
+    }
+}
+
+module! {
+    /// The `anvil` namespace for controlling Anvil development nodes.
+    pub mod anvil {
+        /// Returns the state of the node as a hex-encoded, gzipped JSON blob
+        /// that can be restored with `anvil_loadState`.
+        pub struct DumpState as "anvil_dumpState"
+            Empty => Vec<u8> [serialization::bytes];
+
+        /// Starts impersonating an account, allowing transactions to be sent
+        /// from it without its private key.
+        pub struct ImpersonateAccount as "anvil_impersonateAccount"
+            (Address,) => ();
+
+        /// Merges a state previously dumped with `anvil_dumpState` into the
+        /// current state of the node.
+        pub struct LoadState as "anvil_loadState"
+            (Vec<u8>,) [serialization::param::bytes] => bool;
+
+        /// Mines the specified number of blocks (defaulting to one), with an
+        /// optional interval in seconds between block timestamps.
+        pub struct Mine as "anvil_mine"
+            (Option<u64>, Option<u64>) [serialization::param::mine] => ();
+
+        /// Resets the node to a fresh local chain or to a new fork. Without
+        /// a configuration, the node is reset to its initial state.
+        pub struct Reset as "anvil_reset"
+            (Option<ResetConfig>,) [serialization::param::optional] => ();
+
+        /// Sets the balance of an account.
+        pub struct SetBalance as "anvil_setBalance"
+            (Address, U256) => ();
+
+        /// Sets the code of an account.
+        pub struct SetCode as "anvil_setCode"
+            (Address, Vec<u8>) [serialization::param::address_bytes] => ();
+
+        /// Sets the nonce of an account.
+        pub struct SetNonce as "anvil_setNonce"
+            (Address, u64) [serialization::param::address_quantity] => ();
+
+        /// Sets the value of a storage slot of an account.
+        pub struct SetStorageAt as "anvil_setStorageAt"
+            (Address, U256, Digest) => bool;
+
+        /// Stops impersonating an account.
+        pub struct StopImpersonatingAccount as "anvil_stopImpersonatingAccount"
+            (Address,) => ();
+    }
+}
+
+module! {
+    /// The `debug` namespace.
+    pub mod debug {
+        /// Returns the RLP encoded block.
+        pub struct GetRawBlock as "debug_getRawBlock"
+            (BlockId,) => Vec<u8> [serialization::bytes];
+
+        /// Returns the RLP encoded block header.
+        pub struct GetRawHeader as "debug_getRawHeader"
+            (BlockId,) => Vec<u8> [serialization::bytes];
+
+        /// Returns the EIP-2718 encoded receipts of a block.
+        pub struct GetRawReceipts as "debug_getRawReceipts"
+            (BlockId,) => Vec<Vec<u8>> [serialization::vec_bytes];
+
+        /// Returns the EIP-2718 encoded transaction with the given hash.
+        pub struct GetRawTransaction as "debug_getRawTransaction"
+            (Digest,) => Vec<u8> [serialization::bytes];
+
+        /// Returns the traces of all transactions in the block with the given
+        /// hash.
+        pub struct TraceBlockByHash as "debug_traceBlockByHash"
+            (Digest, TraceConfig) => Vec<TransactionTrace>;
+
+        /// Returns the traces of all transactions in the block with the given
+        /// number.
+        pub struct TraceBlockByNumber as "debug_traceBlockByNumber"
+            (BlockSpec, TraceConfig) => Vec<TransactionTrace>;
+
+        /// Executes a call and returns its trace, in the context of the
+        /// specified block.
+        pub struct TraceCall as "debug_traceCall"
+            (Transaction, BlockId, TraceConfig) => Trace;
+
+        /// Returns the trace of the transaction with the given hash.
+        pub struct TraceTransaction as "debug_traceTransaction"
+            (Digest, TraceConfig) => Trace;
+    }
+}
+
+module! {
+    /// The `engine` namespace.
+    ///
+    /// Note that the Engine API is usually only exposed on an authenticated
+    /// port of the execution client.
+    pub mod engine {
+        /// Exchanges the lists of supported Engine API methods with the
+        /// execution client.
+        pub struct ExchangeCapabilities as "engine_exchangeCapabilities"
+            (Vec<String>,) => Vec<String>;
+
+        /// Updates the fork choice state, optionally starting a payload build
+        /// process.
+        pub struct ForkchoiceUpdatedV1 as "engine_forkchoiceUpdatedV1"
+            (ForkchoiceState, Option<PayloadAttributes>) => ForkchoiceUpdatedResult;
+
+        /// Updates the fork choice state, optionally starting a payload build
+        /// process with withdrawals.
+        pub struct ForkchoiceUpdatedV2 as "engine_forkchoiceUpdatedV2"
+            (ForkchoiceState, Option<PayloadAttributes>) => ForkchoiceUpdatedResult;
+
+        /// Updates the fork choice state, optionally starting a payload build
+        /// process with withdrawals and a parent beacon block root.
+        pub struct ForkchoiceUpdatedV3 as "engine_forkchoiceUpdatedV3"
+            (ForkchoiceState, Option<PayloadAttributes>) => ForkchoiceUpdatedResult;
+
+        /// Returns the blobs and proofs for the specified versioned hashes from
+        /// the transaction pool.
+        pub struct GetBlobsV1 as "engine_getBlobsV1"
+            (Vec<Digest>,) => Vec<Option<BlobAndProof>>;
+
+        /// Returns the execution payload built by a payload build process.
+        pub struct GetPayloadV1 as "engine_getPayloadV1"
+            (PayloadId,) => ExecutionPayload;
+
+        /// Returns the execution payload and block value built by a payload
+        /// build process.
+        pub struct GetPayloadV2 as "engine_getPayloadV2"
+            (PayloadId,) => ExecutionPayloadEnvelope;
+
+        /// Returns the execution payload, block value and blobs bundle built by
+        /// a payload build process.
+        pub struct GetPayloadV3 as "engine_getPayloadV3"
+            (PayloadId,) => ExecutionPayloadEnvelope;
+
+        /// Returns the execution payload, block value, blobs bundle and
+        /// execution requests built by a payload build process.
+        pub struct GetPayloadV4 as "engine_getPayloadV4"
+            (PayloadId,) => ExecutionPayloadEnvelope;
+
+        /// Validates and executes a new payload.
+        pub struct NewPayloadV1 as "engine_newPayloadV1"
+            (ExecutionPayload,) => PayloadStatus;
+
+        /// Validates and executes a new payload with withdrawals.
+        pub struct NewPayloadV2 as "engine_newPayloadV2"
+            (ExecutionPayload,) => PayloadStatus;
+
+        /// Validates and executes a new payload with blob versioned hashes and
+        /// a parent beacon block root.
+        pub struct NewPayloadV3 as "engine_newPayloadV3"
+            (ExecutionPayload, Vec<Digest>, Digest) => PayloadStatus;
+
+        /// Validates and executes a new payload with blob versioned hashes, a
+        /// parent beacon block root and execution requests.
+        pub struct NewPayloadV4 as "engine_newPayloadV4"
+            (ExecutionPayload, Vec<Digest>, Digest, Vec<Vec<u8>>) [serialization::param::engine_new_payload_v4] => PayloadStatus;
+    }
+}
+
+module! {
+    /// The `evm` namespace for controlling development nodes, supported by
+    /// both Anvil and Hardhat.
+    pub mod evm {
+        /// Advances the time of the node by the specified number of seconds.
+        /// Returns the total time adjustment, which is encoded differently
+        /// depending on the node.
+        pub struct IncreaseTime as "evm_increaseTime"
+            (u64,) [serialization::param::quantity] => jsonrpc::Value [serialization::value];
+
+        /// Reverts the state of the node to a snapshot. Note that snapshots
+        /// can only be reverted to once.
+        pub struct Revert as "evm_revert"
+            (U256,) => bool;
+
+        /// Sets the timestamp of the next block. The result is encoded
+        /// differently depending on the node.
+        pub struct SetNextBlockTimestamp as "evm_setNextBlockTimestamp"
+            (u64,) [serialization::param::quantity] => jsonrpc::Value [serialization::value];
+
+        /// Takes a snapshot of the state of the node, returning its ID.
+        pub struct Snapshot as "evm_snapshot"
+            Empty => U256;
+    }
+}
+
+module! {
+    /// The `hardhat` namespace for controlling Hardhat Network development
+    /// nodes. These methods are also supported by Anvil.
+    pub mod hardhat {
+        /// Starts impersonating an account, allowing transactions to be sent
+        /// from it without its private key.
+        pub struct ImpersonateAccount as "hardhat_impersonateAccount"
+            (Address,) => bool;
+
+        /// Mines the specified number of blocks (defaulting to one), with an
+        /// optional interval in seconds between block timestamps.
+        pub struct Mine as "hardhat_mine"
+            (Option<u64>, Option<u64>) [serialization::param::mine] => bool;
+
+        /// Resets the node to a fresh local chain or to a new fork. Without
+        /// a configuration, the node is reset to its initial state.
+        pub struct Reset as "hardhat_reset"
+            (Option<ResetConfig>,) [serialization::param::optional] => bool;
+
+        /// Sets the balance of an account.
+        pub struct SetBalance as "hardhat_setBalance"
+            (Address, U256) => bool;
+
+        /// Sets the code of an account.
+        pub struct SetCode as "hardhat_setCode"
+            (Address, Vec<u8>) [serialization::param::address_bytes] => bool;
+
+        /// Sets the nonce of an account.
+        pub struct SetNonce as "hardhat_setNonce"
+            (Address, u64) [serialization::param::address_quantity] => bool;
+
+        /// Sets the value of a storage slot of an account.
+        pub struct SetStorageAt as "hardhat_setStorageAt"
+            (Address, U256, Digest) => bool;
+
+        /// Stops impersonating an account.
+        pub struct StopImpersonatingAccount as "hardhat_stopImpersonatingAccount"
+            (Address,) => bool;
     }
 }
 
 module! {
     /// The `net` namespace.
     pub mod net {
+        /// Returns whether or not the client is actively listening for network
+        /// connections.
+        pub struct Listening as "net_listening"
+            Empty => bool;
+
+        /// Returns the number of peers currently connected to the client.
+        pub struct PeerCount as "net_peerCount"
+            Empty => u64 [serialization::num];
+
         /// Returns the current network ID. This is usually equivalent to the
         /// chainID, but may differ from it for some legacy networks or special
         /// testnets.
@@ -221,3 +578,81 @@ module! {
             Empty => U256 [ethprim::num::serde::decimal];
     }
 }
+
+module! {
+    /// The `trace` namespace.
+    ///
+    /// These methods were introduced by OpenEthereum, and are supported by
+    /// some execution clients such as Erigon and Reth.
+    pub mod trace {
+        /// Returns the action traces of all transactions in a block.
+        pub struct Block as "trace_block"
+            (BlockSpec,) => Option<Vec<LocalizedTrace>>;
+
+        /// Executes a call and returns the requested traces, in the context of
+        /// the specified block.
+        pub struct Call as "trace_call"
+            (types::Transaction, Vec<TraceType>, BlockId) => TraceResults;
+
+        /// Executes a sequence of calls building on each other's state and
+        /// returns the requested traces, in the context of the specified
+        /// block.
+        pub struct CallMany as "trace_callMany"
+            (Vec<(types::Transaction, Vec<TraceType>)>, BlockId) => Vec<TraceResults>;
+
+        /// Returns the action traces matching the specified filter.
+        pub struct Filter as "trace_filter"
+            (TraceFilter,) => Vec<LocalizedTrace>;
+
+        /// Replays all transactions in a block and returns the requested
+        /// traces.
+        pub struct ReplayBlockTransactions as "trace_replayBlockTransactions"
+            (BlockSpec, Vec<TraceType>) => Option<Vec<TraceResults>>;
+
+        /// Replays a transaction and returns the requested traces.
+        pub struct ReplayTransaction as "trace_replayTransaction"
+            (Digest, Vec<TraceType>) => TraceResults;
+
+        /// Returns the action traces of a transaction.
+        pub struct Transaction as "trace_transaction"
+            (Digest,) => Option<Vec<LocalizedTrace>>;
+    }
+}
+
+module! {
+    /// The `txpool` namespace.
+    pub mod txpool {
+        /// Returns the pending and queued transactions in the transaction
+        /// pool.
+        pub struct Content as "txpool_content"
+            Empty => TxpoolContent;
+
+        /// Returns the pending and queued transactions in the transaction
+        /// pool from the specified sender.
+        pub struct ContentFrom as "txpool_contentFrom"
+            (Address,) => TxpoolContentFrom;
+
+        /// Returns a textual summary of the pending and queued transactions
+        /// in the transaction pool.
+        pub struct Inspect as "txpool_inspect"
+            Empty => TxpoolInspect;
+
+        /// Returns the number of pending and queued transactions in the
+        /// transaction pool.
+        pub struct Status as "txpool_status"
+            Empty => TxpoolStatus;
+    }
+}
+
+module! {
+    /// The `web3` namespace.
+    pub mod web3 {
+        /// Returns the current client version.
+        pub struct ClientVersion as "web3_clientVersion"
+            Empty => types::ClientVersion;
+
+        /// Returns the Keccak-256 hash of the given data.
+        pub struct Sha3 as "web3_sha3"
+            (Vec<u8>,) [serialization::param::bytes] => Digest;
+    }
+}
@@ -0,0 +1,358 @@
+//! Metrics hooks for JSON RPC clients.
+//!
+//! Clients can be configured with a [`Metrics`] implementation that gets
+//! notified of every JSON RPC call and every HTTP request sent to the node.
+//! This allows tracking which methods are being called, how often they fail,
+//! and how large requests and responses are.
+//!
+//! An adapter for the [`metrics`](https://docs.rs/metrics) crate is available
+//! with the `metrics` feature.
+
+use crate::jsonrpc::ErrorCode;
+#[cfg(any(feature = "curl", feature = "http"))]
+use crate::jsonrpc::{Request as JsonRequest, Response as JsonResponse};
+use std::time::Duration;
+#[cfg(any(feature = "curl", feature = "http"))]
+use std::{
+    sync::atomic::{AtomicU32, AtomicUsize, Ordering},
+    time::Instant,
+};
+
+/// A sink for client metrics.
+///
+/// All methods have empty default implementations, so implementations only
+/// need to handle the metrics that they are interested in.
+pub trait Metrics: Send + Sync {
+    /// Called for every completed JSON RPC call. Note that this is called once
+    /// for each call in a batch.
+    fn call(&self, call: &Call) {
+        let _ = call;
+    }
+
+    /// Called for every HTTP request sent to the node, which can either be a
+    /// single JSON RPC call or a batch.
+    fn request(&self, request: &Request) {
+        let _ = request;
+    }
+
+    /// Called by the buffered HTTP client with the number of calls that were
+    /// coalesced into a single request.
+    fn coalesced(&self, calls: usize) {
+        let _ = calls;
+    }
+}
+
+/// A completed JSON RPC call.
+#[derive(Clone, Debug)]
+pub struct Call<'a> {
+    /// The JSON RPC method name.
+    pub method: &'a str,
+    /// The outcome of the call.
+    pub outcome: Outcome,
+    /// The time it took to complete the call. For batched calls, this is the
+    /// latency of the entire batch.
+    pub latency: Duration,
+}
+
+/// An HTTP request sent to the node.
+#[derive(Clone, Debug)]
+pub struct Request {
+    /// The number of calls in the batch, or `None` if the request was a
+    /// single call.
+    pub batch: Option<usize>,
+    /// The HTTP status code of the response, if one was received.
+    pub status: Option<u32>,
+    /// Whether or not the request succeeded at the transport level. Note that
+    /// individual calls may still have failed with JSON RPC errors.
+    pub success: bool,
+    /// The time it took to complete the request.
+    pub latency: Duration,
+    /// The size of the request body in bytes.
+    pub request_size: usize,
+    /// The size of the response body in bytes.
+    pub response_size: usize,
+}
+
+/// The outcome of a JSON RPC call.
+#[derive(Clone, Copy, Debug)]
+pub enum Outcome {
+    /// The call succeeded.
+    Success,
+    /// The node responded with a JSON RPC error.
+    Error(ErrorCode),
+    /// The node responded with an unsuccessful HTTP status code.
+    Status(u32),
+    /// The call failed for some other reason, such as a connection error or
+    /// an invalid response.
+    Failure,
+}
+
+impl Outcome {
+    /// Returns a short label describing the outcome.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Success => "success".to_owned(),
+            Self::Error(code) => i32::from(*code).to_string(),
+            Self::Status(status) => format!("http_{status}"),
+            Self::Failure => "failure".to_owned(),
+        }
+    }
+}
+
+/// An in-progress measurement of a single request to the node.
+///
+/// Atomics are used so that the probe can be shared across `await` points in
+/// `Send` futures. A status of `0` means no response was received.
+#[cfg(any(feature = "curl", feature = "http"))]
+pub(crate) struct Probe<'a> {
+    metrics: Option<&'a dyn Metrics>,
+    start: Instant,
+    status: AtomicU32,
+    request_size: AtomicUsize,
+    response_size: AtomicUsize,
+}
+
+#[cfg(any(feature = "curl", feature = "http"))]
+impl<'a> Probe<'a> {
+    /// Starts a new measurement.
+    pub(crate) fn start(metrics: Option<&'a dyn Metrics>) -> Self {
+        Self {
+            metrics,
+            start: Instant::now(),
+            status: AtomicU32::default(),
+            request_size: AtomicUsize::default(),
+            response_size: AtomicUsize::default(),
+        }
+    }
+
+    /// Records the size of the sent request body.
+    pub(crate) fn sent(&self, size: usize) {
+        self.request_size.store(size, Ordering::Relaxed);
+    }
+
+    /// Records the HTTP status code of the response.
+    pub(crate) fn status(&self, status: u32) {
+        self.status.store(status, Ordering::Relaxed);
+    }
+
+    /// Records the size of the received response body.
+    pub(crate) fn received(&self, size: usize) {
+        self.response_size.store(size, Ordering::Relaxed);
+    }
+
+    /// Reports the result of a single JSON RPC call.
+    pub(crate) fn finish_call<E>(&self, request: &JsonRequest, result: &Result<JsonResponse, E>) {
+        let Some(metrics) = self.metrics else {
+            return;
+        };
+        let latency = self.start.elapsed();
+        metrics.request(&self.request(None, result.is_ok(), latency));
+        metrics.call(&Call {
+            method: request.method.as_str(),
+            outcome: match result {
+                Ok(response) => outcome(response),
+                Err(_) => self.failure(),
+            },
+            latency,
+        });
+    }
+
+    /// Reports the result of a JSON RPC batch.
+    pub(crate) fn finish_batch<E>(
+        &self,
+        requests: &[JsonRequest],
+        result: &Result<Vec<JsonResponse>, E>,
+    ) {
+        let Some(metrics) = self.metrics else {
+            return;
+        };
+        let latency = self.start.elapsed();
+        metrics.request(&self.request(Some(requests.len()), result.is_ok(), latency));
+        for request in requests {
+            let outcome = match result {
+                Ok(responses) => responses
+                    .iter()
+                    .find(|response| response.id == Some(request.id))
+                    .map(outcome)
+                    .unwrap_or(Outcome::Failure),
+                Err(_) => self.failure(),
+            };
+            metrics.call(&Call {
+                method: request.method.as_str(),
+                outcome,
+                latency,
+            });
+        }
+    }
+
+    fn request(&self, batch: Option<usize>, success: bool, latency: Duration) -> Request {
+        Request {
+            batch,
+            status: self.response_status(),
+            success,
+            latency,
+            request_size: self.request_size.load(Ordering::Relaxed),
+            response_size: self.response_size.load(Ordering::Relaxed),
+        }
+    }
+
+    fn response_status(&self) -> Option<u32> {
+        Some(self.status.load(Ordering::Relaxed)).filter(|status| *status != 0)
+    }
+
+    fn failure(&self) -> Outcome {
+        match self.response_status() {
+            Some(status) if !(200..300).contains(&status) => Outcome::Status(status),
+            _ => Outcome::Failure,
+        }
+    }
+}
+
+/// Returns the outcome of a JSON RPC response.
+#[cfg(any(feature = "curl", feature = "http"))]
+fn outcome(response: &JsonResponse) -> Outcome {
+    match &response.result {
+        Ok(_) => Outcome::Success,
+        Err(err) => Outcome::Error(err.code),
+    }
+}
+
+/// A [`Metrics`] implementation that reports to the global recorder of the
+/// [`metrics`](https://docs.rs/metrics) crate.
+///
+/// The following metrics are reported:
+///
+/// - `ethrpc_calls_total` counter, labeled by `method` and `outcome`
+/// - `ethrpc_call_duration_seconds` histogram, labeled by `method`
+/// - `ethrpc_requests_total` counter, labeled by `kind` (`call` or `batch`) and
+///   `status`
+/// - `ethrpc_request_size_bytes` and `ethrpc_response_size_bytes` histograms,
+///   labeled by `kind`
+/// - `ethrpc_batch_size` histogram
+/// - `ethrpc_coalesced_calls` histogram
+#[cfg(feature = "metrics")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Facade;
+
+#[cfg(feature = "metrics")]
+impl Metrics for Facade {
+    fn call(&self, call: &Call) {
+        let method = call.method.to_owned();
+        ::metrics::counter!(
+            "ethrpc_calls_total",
+            "method" => method.clone(),
+            "outcome" => call.outcome.label(),
+        )
+        .increment(1);
+        ::metrics::histogram!("ethrpc_call_duration_seconds", "method" => method)
+            .record(call.latency.as_secs_f64());
+    }
+
+    fn request(&self, request: &Request) {
+        let kind = if request.batch.is_some() {
+            "batch"
+        } else {
+            "call"
+        };
+        let status = match request.status {
+            Some(status) => status.to_string(),
+            None => "none".to_owned(),
+        };
+        ::metrics::counter!("ethrpc_requests_total", "kind" => kind, "status" => status)
+            .increment(1);
+        ::metrics::histogram!("ethrpc_request_size_bytes", "kind" => kind)
+            .record(request.request_size as f64);
+        ::metrics::histogram!("ethrpc_response_size_bytes", "kind" => kind)
+            .record(request.response_size as f64);
+        if let Some(size) = request.batch {
+            ::metrics::histogram!("ethrpc_batch_size").record(size as f64);
+        }
+    }
+
+    fn coalesced(&self, calls: usize) {
+        ::metrics::histogram!("ethrpc_coalesced_calls").record(calls as f64);
+    }
+}
+
+#[cfg(all(test, any(feature = "curl", feature = "http")))]
+mod tests {
+    use super::*;
+    use crate::{
+        eth,
+        jsonrpc::{self, Value},
+        types::Empty,
+    };
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Mutex<Vec<(String, String)>>,
+        requests: Mutex<Vec<Request>>,
+    }
+
+    impl Metrics for Recorder {
+        fn call(&self, call: &Call) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((call.method.to_owned(), call.outcome.label()));
+        }
+
+        fn request(&self, request: &Request) {
+            self.requests.lock().unwrap().push(request.clone());
+        }
+    }
+
+    #[test]
+    fn reports_batch_outcomes() {
+        let metrics = Recorder::default();
+        let requests = vec![
+            JsonRequest::new(eth::BlockNumber, Empty).unwrap(),
+            JsonRequest::new(eth::ChainId, Empty).unwrap(),
+        ];
+        let responses = vec![
+            JsonResponse {
+                jsonrpc: jsonrpc::Version::V2,
+                result: Err(jsonrpc::Error {
+                    code: ErrorCode::MethodNotFound,
+                    message: "not found".to_owned(),
+                    data: Value::default(),
+                }),
+                id: Some(requests[1].id),
+            },
+            JsonResponse {
+                jsonrpc: jsonrpc::Version::V2,
+                result: Ok(Value(json!("0x2a"))),
+                id: Some(requests[0].id),
+            },
+        ];
+
+        let probe = Probe::start(Some(&metrics));
+        probe.sent(100);
+        probe.status(200);
+        probe.received(200);
+        probe.finish_batch(&requests, &Ok::<_, ()>(responses));
+
+        let probe = Probe::start(Some(&metrics));
+        probe.status(429);
+        probe.finish_call(&requests[0], &Err::<JsonResponse, _>(()));
+
+        assert_eq!(
+            *metrics.calls.lock().unwrap(),
+            [
+                ("eth_blockNumber".to_owned(), "success".to_owned()),
+                ("eth_chainId".to_owned(), "-32601".to_owned()),
+                ("eth_blockNumber".to_owned(), "http_429".to_owned()),
+            ],
+        );
+        let requests = metrics.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].batch, Some(2));
+        assert_eq!(
+            (requests[0].request_size, requests[0].response_size),
+            (100, 200)
+        );
+        assert!(!requests[1].success);
+    }
+}
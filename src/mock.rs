@@ -0,0 +1,383 @@
+//! Mock JSON RPC transport for testing.
+//!
+//! This module provides a [`Mock`] transport that returns scripted responses
+//! for expected calls and records all requests it receives. It implements both
+//! [`Transport`] and [`BlockingTransport`], so it can be used for testing code
+//! that uses either the `async` or blocking call paths.
+//!
+//! This module is only available with the `mock` feature, which is meant to be
+//! enabled for `dev-dependencies`.
+
+use crate::{
+    jsonrpc::{self, JsonError, Request, Response, Value, batch},
+    method::Method,
+    transport::{BlockingTransport, Transport},
+};
+use std::{collections::VecDeque, marker::PhantomData, sync::Mutex};
+#[cfg(test)]
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+use thiserror::Error;
+
+/// A mock JSON RPC transport.
+#[derive(Default)]
+pub struct Mock {
+    expectations: Mutex<Vec<Expectation>>,
+    batches: Mutex<VecDeque<Vec<String>>>,
+    requests: Mutex<Vec<Request>>,
+}
+
+impl Mock {
+    /// Creates a new mock transport without any expectations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an expectation for calls to the specified method.
+    ///
+    /// Requests are matched against expectations in the order that they were
+    /// added. By default, an expectation matches any parameters, any number of
+    /// times, and returns a `null` result.
+    pub fn expect<M>(&mut self, method: M) -> Expect<'_, M>
+    where
+        M: Method,
+    {
+        let expectations = self.expectations.get_mut().unwrap();
+        expectations.push(Expectation {
+            method: method.name().into_owned(),
+            params: None,
+            result: Ok(Ok(Value::default())),
+            times: None,
+            calls: 0,
+        });
+        Expect {
+            expectation: expectations.last_mut().unwrap(),
+            _method: PhantomData,
+        }
+    }
+
+    /// Adds an expectation for a batch request with the specified methods.
+    ///
+    /// Batch expectations are matched in the order that they were added. Once
+    /// any batch expectations are added, every batch request needs to match
+    /// the next expected batch. Note that the individual requests in the batch
+    /// are still matched against the method expectations.
+    pub fn expect_batch<I>(&mut self, methods: I)
+    where
+        I: IntoIterator,
+        I::Item: Method,
+    {
+        self.batches.get_mut().unwrap().push_back(
+            methods
+                .into_iter()
+                .map(|method| method.name().into_owned())
+                .collect(),
+        );
+    }
+
+    /// Returns all requests that were received, in order.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Verifies that all expectations were met.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if an expectation was not called the expected
+    /// number of times, or if an expected batch was not received.
+    pub fn verify(&self) {
+        for expectation in self.expectations.lock().unwrap().iter() {
+            if let Some(times) = expectation.times {
+                assert_eq!(
+                    expectation.calls, times,
+                    "expected {times} calls to {} but got {}",
+                    expectation.method, expectation.calls,
+                );
+            }
+        }
+        let batches = self.batches.lock().unwrap();
+        assert!(batches.is_empty(), "expected batches {batches:?}");
+    }
+
+    fn respond(&self, request: Request) -> Result<Response, Error> {
+        self.requests.lock().unwrap().push(request.clone());
+
+        let mut expectations = self.expectations.lock().unwrap();
+        let expectation = expectations
+            .iter_mut()
+            .find(|expectation| expectation.matches(&request))
+            .ok_or_else(|| {
+                Error::Unexpected(format!("{}({})", request.method.as_str(), request.params.0))
+            })?;
+        expectation.calls += 1;
+
+        Ok(Response {
+            jsonrpc: jsonrpc::Version::V2,
+            result: expectation.result.clone().map_err(Error::Failure)?,
+            id: Some(request.id),
+        })
+    }
+
+    fn respond_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Error> {
+        let methods = requests
+            .iter()
+            .map(|request| request.method.as_str().to_owned())
+            .collect::<Vec<_>>();
+        {
+            let mut batches = self.batches.lock().unwrap();
+            if let Some(expected) = batches.front() {
+                if *expected != methods {
+                    return Err(Error::Unexpected(format!(
+                        "batch {methods:?}, expected {expected:?}"
+                    )));
+                }
+                batches.pop_front();
+            }
+        }
+
+        requests
+            .into_iter()
+            .map(|request| self.respond(request))
+            .collect()
+    }
+}
+
+impl Transport for Mock {
+    type Error = Error;
+
+    async fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        self.respond(request)
+    }
+
+    async fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        self.respond_batch(requests)
+    }
+}
+
+impl BlockingTransport for Mock {
+    type Error = Error;
+
+    fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        self.respond(request)
+    }
+
+    fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        self.respond_batch(requests)
+    }
+}
+
+/// A predicate over serialized request parameters.
+type ParamsMatcher = Box<dyn Fn(&Value) -> bool + Send + Sync>;
+
+/// An expected method call.
+struct Expectation {
+    method: String,
+    params: Option<ParamsMatcher>,
+    result: Result<Result<Value, jsonrpc::Error>, String>,
+    times: Option<usize>,
+    calls: usize,
+}
+
+impl Expectation {
+    fn matches(&self, request: &Request) -> bool {
+        self.method == request.method.as_str()
+            && self.times.is_none_or(|times| self.calls < times)
+            && self
+                .params
+                .as_ref()
+                .is_none_or(|params| params(&request.params))
+    }
+}
+
+/// A builder for configuring an expected method call.
+pub struct Expect<'a, M> {
+    expectation: &'a mut Expectation,
+    _method: PhantomData<M>,
+}
+
+impl<M> Expect<'_, M>
+where
+    M: Method,
+{
+    /// Only match calls with the specified parameters.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the parameters fail to serialize.
+    pub fn with_params(self, params: M::Params) -> Self {
+        let expected =
+            Value::for_params::<M>(params).expect("failed to serialize expected parameters");
+        self.expectation.params = Some(Box::new(move |params| params.0 == expected.0));
+        self
+    }
+
+    /// Only match calls with parameters that satisfy the specified predicate.
+    pub fn with_params_matching<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&M::Params) -> bool + Send + Sync + 'static,
+    {
+        self.expectation.params = Some(Box::new(move |params| {
+            M::deserialize_params(&params.0).is_ok_and(|params| predicate(&params))
+        }));
+        self
+    }
+
+    /// Only match the specified number of calls, where [`Mock::verify`] checks
+    /// that exactly this many calls were made.
+    pub fn times(self, times: usize) -> Self {
+        self.expectation.times = Some(times);
+        self
+    }
+
+    /// Returns the specified result.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the result fails to serialize.
+    pub fn returns(self, result: M::Result) -> Self {
+        let result = Value::for_result::<M>(result).expect("failed to serialize result");
+        self.expectation.result = Ok(Ok(result));
+        self
+    }
+
+    /// Returns the specified JSON RPC error.
+    pub fn returns_error(self, err: jsonrpc::Error) -> Self {
+        self.expectation.result = Ok(Err(err));
+        self
+    }
+
+    /// Fails the roundtrip with a transport error with the specified message.
+    pub fn fails(self, message: impl Into<String>) -> Self {
+        self.expectation.result = Err(message.into());
+        self
+    }
+}
+
+/// A mock transport error.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON error: {0}")]
+    Json(#[from] JsonError),
+    #[error("unexpected request: {0}")]
+    Unexpected(String),
+    #[error("mock transport failure: {0}")]
+    Failure(String),
+    #[error(transparent)]
+    Rpc(#[from] jsonrpc::Error),
+    #[error(transparent)]
+    Batch(#[from] batch::Error),
+}
+
+/// Polls a future that is expected to be immediately ready, such as calls to
+/// a [`Mock`] transport.
+///
+/// # Panics
+///
+/// This function panics if the future is pending.
+#[cfg(test)]
+pub(crate) fn ready<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future not ready"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth,
+        jsonrpc::ErrorCode,
+        transport::{BlockingTransportExt, TransportExt},
+        types::{BlockTag, Empty},
+    };
+    use ethprim::{address, uint};
+
+    #[test]
+    fn scripted_responses() {
+        let account = address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41");
+        let mut mock = Mock::new();
+        mock.expect(eth::GetBalance)
+            .with_params((account, BlockTag::Latest.into()))
+            .returns(uint!("42"))
+            .times(2);
+        mock.expect(eth::GetBalance)
+            .with_params_matching(|(_, block)| *block == BlockTag::Safe.into())
+            .returns_error(jsonrpc::Error::custom("unsafe"));
+        mock.expect(eth::ChainId).fails("connection refused");
+
+        for _ in 0..2 {
+            assert_eq!(
+                BlockingTransportExt::call(
+                    &mock,
+                    eth::GetBalance,
+                    (account, BlockTag::Latest.into())
+                )
+                .unwrap(),
+                uint!("42"),
+            );
+        }
+        assert!(matches!(
+            ready(TransportExt::call(
+                &mock,
+                eth::GetBalance,
+                (account, BlockTag::Latest.into())
+            )),
+            Err(Error::Unexpected(_)),
+        ));
+        assert!(matches!(
+            ready(TransportExt::call(
+                &mock,
+                eth::GetBalance,
+                (account, BlockTag::Safe.into())
+            )),
+            Err(Error::Rpc(jsonrpc::Error {
+                code: ErrorCode::ServerError(-32000),
+                ..
+            })),
+        ));
+        assert!(matches!(
+            BlockingTransportExt::call_np(&mock, eth::ChainId),
+            Err(Error::Failure(_)),
+        ));
+
+        assert_eq!(mock.requests().len(), 5);
+        mock.verify();
+    }
+
+    #[test]
+    fn batch_shapes() {
+        let mut mock = Mock::new();
+        mock.expect(eth::BlockNumber).returns(42);
+        mock.expect(eth::ChainId).returns(1);
+        mock.expect_batch(["eth_blockNumber", "eth_chainId"]);
+
+        assert!(matches!(
+            BlockingTransportExt::batch(&mock, ((eth::ChainId, Empty), (eth::BlockNumber, Empty))),
+            Err(Error::Unexpected(_)),
+        ));
+        assert_eq!(
+            ready(TransportExt::batch(
+                &mock,
+                ((eth::BlockNumber, Empty), (eth::ChainId, Empty))
+            ))
+            .unwrap(),
+            (42, 1),
+        );
+        mock.verify();
+    }
+
+    #[test]
+    #[should_panic]
+    fn verify_call_counts() {
+        let mut mock = Mock::new();
+        mock.expect(eth::BlockNumber).returns(42).times(1);
+        mock.verify();
+    }
+}
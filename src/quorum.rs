@@ -0,0 +1,236 @@
+//! Quorum JSON RPC client.
+//!
+//! This module provides a client for cross-checking reads across multiple
+//! endpoints. Calls are sent to all endpoints concurrently, and a result is
+//! only returned once enough endpoints agree on it. This is useful for
+//! high-value reads where no single node provider is trusted.
+
+use crate::{
+    jsonrpc::{self, JsonError, Request, Response},
+    method::Method,
+    transport::Transport,
+};
+use futures::{StreamExt as _, stream::FuturesUnordered};
+use serde::Serialize;
+use thiserror::Error;
+
+/// A JSON RPC client that requires a quorum of endpoints to agree on results.
+///
+/// Endpoints that respond with identical JSON RPC errors (such as the same
+/// `eth_call` revert) also agree, in which case that error is returned.
+///
+/// Note that quorum clients only support individual calls. Since results are
+/// compared after being deserialized for a specific method, they don't
+/// implement [`Transport`] and batches are not supported.
+pub struct Quorum<T> {
+    endpoints: Vec<T>,
+    threshold: usize,
+}
+
+impl<T> Quorum<T> {
+    /// Creates a new quorum client for the specified endpoints, where at least
+    /// `threshold` of them need to agree on a result.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the threshold is 0 or exceeds the number of
+    /// endpoints.
+    pub fn new(endpoints: impl IntoIterator<Item = T>, threshold: usize) -> Self {
+        let endpoints = endpoints.into_iter().collect::<Vec<_>>();
+        assert!(
+            (1..=endpoints.len()).contains(&threshold),
+            "quorum threshold must be between 1 and the number of endpoints"
+        );
+        Self {
+            endpoints,
+            threshold,
+        }
+    }
+
+    /// Returns the endpoints.
+    pub fn endpoints(&self) -> &[T] {
+        &self.endpoints
+    }
+}
+
+impl<T> Quorum<T>
+where
+    T: Transport,
+{
+    /// Executes a JSON RPC call on all endpoints, returning the result once
+    /// `threshold` endpoints agree on it.
+    ///
+    /// Note that results are compared after deserialization, so equivalent
+    /// JSON encodings (such as differences in hex number formatting) are
+    /// considered equal. JSON RPC errors agree if their code, message and data
+    /// are identical.
+    pub async fn call<M>(
+        &self,
+        method: M,
+        params: M::Params,
+    ) -> Result<M::Result, Error<M::Result, T::Error>>
+    where
+        M: Method + Serialize,
+        M::Result: PartialEq,
+    {
+        let request = Request::new(method, params)?;
+        let mut pending = self
+            .endpoints
+            .iter()
+            .enumerate()
+            .map(|(index, endpoint)| {
+                let request = request.clone();
+                async move { (index, answer::<M, _>(endpoint.roundtrip(request).await)) }
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        let mut answers = self.endpoints.iter().map(|_| None).collect::<Vec<_>>();
+        while let Some((index, answer)) = pending.next().await {
+            answers[index] = Some(answer);
+            let Some(Ok(result)) = &answers[index] else {
+                continue;
+            };
+            let agreeing = answers
+                .iter()
+                .filter(|answer| matches!(answer, Some(Ok(other)) if agree(other, result)))
+                .count();
+            if agreeing >= self.threshold {
+                return match answers.swap_remove(index) {
+                    Some(Ok(Ok(result))) => Ok(result),
+                    Some(Ok(Err(err))) => Err(Error::Rpc(err)),
+                    _ => unreachable!(),
+                };
+            }
+        }
+
+        Err(Error::NoQuorum(
+            answers
+                .into_iter()
+                .flatten()
+                .map(|answer| answer?.map_err(T::Error::from))
+                .collect(),
+        ))
+    }
+}
+
+/// Extracts an endpoint's answer from its response. JSON RPC errors are kept
+/// separate from transport errors, as endpoints can agree on them.
+fn answer<M, E>(response: Result<Response, E>) -> Result<Result<M::Result, jsonrpc::Error>, E>
+where
+    M: Method,
+    E: From<JsonError>,
+{
+    Ok(response?.result::<M>()?)
+}
+
+/// Returns whether or not two answers agree.
+fn agree<R>(a: &Result<R, jsonrpc::Error>, b: &Result<R, jsonrpc::Error>) -> bool
+where
+    R: PartialEq,
+{
+    match (a, b) {
+        (Ok(a), Ok(b)) => a == b,
+        (Err(a), Err(b)) => {
+            i32::from(a.code) == i32::from(b.code) && a.message == b.message && a.data == b.data
+        }
+        _ => false,
+    }
+}
+
+/// A quorum client error.
+#[derive(Debug, Error)]
+pub enum Error<R, E> {
+    #[error("JSON error: {0}")]
+    Json(#[from] JsonError),
+    /// Enough endpoints responded with the same JSON RPC error.
+    #[error(transparent)]
+    Rpc(jsonrpc::Error),
+    /// Not enough endpoints agreed on a result. This contains the answers of
+    /// each endpoint, in the order they were specified.
+    #[error("endpoints did not reach a quorum: {0:?}")]
+    NoQuorum(Vec<Result<R, E>>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eth, mock::Mock, types::Empty};
+    use futures::executor;
+
+    fn endpoint(block_number: Option<u64>) -> Mock {
+        let mut mock = Mock::new();
+        match block_number {
+            Some(block_number) => mock.expect(eth::BlockNumber).returns(block_number),
+            None => mock.expect(eth::BlockNumber).fails("endpoint down"),
+        };
+        mock
+    }
+
+    fn reverting(message: &str) -> Mock {
+        let mut mock = Mock::new();
+        mock.expect(eth::BlockNumber)
+            .returns_error(jsonrpc::Error::custom(message));
+        mock
+    }
+
+    #[test]
+    fn returns_agreed_result() {
+        let client = Quorum::new(
+            [
+                endpoint(Some(1)),
+                endpoint(None),
+                endpoint(Some(42)),
+                endpoint(Some(42)),
+            ],
+            2,
+        );
+        assert_eq!(
+            executor::block_on(client.call(eth::BlockNumber, Empty)).unwrap(),
+            42,
+        );
+    }
+
+    #[test]
+    fn reports_answers_without_quorum() {
+        let client = Quorum::new([endpoint(Some(1)), endpoint(None), endpoint(Some(42))], 2);
+        let Err(Error::NoQuorum(answers)) =
+            executor::block_on(client.call(eth::BlockNumber, Empty))
+        else {
+            panic!("unexpected quorum");
+        };
+        assert!(matches!(
+            &answers[..],
+            [Ok(1), Err(crate::mock::Error::Failure(_)), Ok(42)],
+        ));
+    }
+
+    #[test]
+    fn returns_agreed_error() {
+        let client = Quorum::new(
+            [
+                reverting("reverted"),
+                endpoint(Some(42)),
+                reverting("reverted"),
+            ],
+            2,
+        );
+        assert!(matches!(
+            executor::block_on(client.call(eth::BlockNumber, Empty)),
+            Err(Error::Rpc(jsonrpc::Error { message, .. })) if message == "reverted",
+        ));
+
+        let client = Quorum::new([reverting("reverted"), reverting("out of gas")], 2);
+        let Err(Error::NoQuorum(answers)) =
+            executor::block_on(client.call(eth::BlockNumber, Empty))
+        else {
+            panic!("unexpected quorum");
+        };
+        assert!(matches!(
+            &answers[..],
+            [
+                Err(crate::mock::Error::Rpc(_)),
+                Err(crate::mock::Error::Rpc(_))
+            ],
+        ));
+    }
+}
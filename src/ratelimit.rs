@@ -0,0 +1,220 @@
+//! Rate limited JSON RPC client.
+//!
+//! This module provides a client that wraps a transport and limits the rate at
+//! which requests are sent to the node. Each method has a configurable cost,
+//! allowing both simple requests-per-second limits as well as compute unit
+//! budgets as used by hosted node providers.
+
+use crate::{
+    jsonrpc::{Request, Response},
+    method::Method,
+    transport::Transport,
+};
+use std::{
+    collections::HashMap,
+    num::NonZeroU64,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// A rate limited JSON RPC client.
+///
+/// Rate limiting is implemented with a token bucket, where calls are charged
+/// the cost of their method and batches are charged the summed cost of their
+/// requests. Calls that exceed the available budget are delayed until enough
+/// budget has been replenished.
+///
+/// Calls and batches that cost more than the configured burst are never
+/// rejected. Instead, they are delayed until the budget they exceed the burst
+/// by has been replenished, and calls that follow are delayed until the
+/// resulting debt has been paid off.
+///
+/// Note that when wrapping an [`crate::http::Buffered`] client, calls are
+/// charged individually as they are buffered, so the batches that it sends to
+/// the node are charged the summed cost of their requests.
+pub struct RateLimit<T> {
+    inner: T,
+    config: Configuration,
+    bucket: Mutex<Bucket>,
+}
+
+impl<T> RateLimit<T> {
+    /// Creates a new rate limited client around the specified transport.
+    pub fn new(inner: T, config: Configuration) -> Self {
+        let bucket = Mutex::new(Bucket::new(&config, Instant::now()));
+        Self {
+            inner,
+            config,
+            bucket,
+        }
+    }
+
+    /// Returns a reference to the inner transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Waits until there is enough budget for the specified cost.
+    async fn acquire(&self, cost: u64) {
+        let delay = self
+            .bucket
+            .lock()
+            .unwrap()
+            .reserve(&self.config, cost, Instant::now());
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+impl<T> Transport for RateLimit<T>
+where
+    T: Transport,
+{
+    type Error = T::Error;
+
+    async fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        self.acquire(self.config.cost(request.method.as_str()))
+            .await;
+        self.inner.roundtrip(request).await
+    }
+
+    async fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        self.acquire(
+            requests
+                .iter()
+                .map(|request| self.config.cost(request.method.as_str()))
+                .sum(),
+        )
+        .await;
+        self.inner.roundtrip_batch(requests).await
+    }
+}
+
+/// Rate limit configuration.
+#[derive(Clone, Debug)]
+pub struct Configuration {
+    /// The budget that is replenished every second.
+    pub rate: NonZeroU64,
+    /// The maximum budget that can accumulate while the client is idle,
+    /// allowing for bursts of calls.
+    pub burst: u64,
+    /// The cost of methods that don't have an explicitly configured cost.
+    pub default_cost: u64,
+    /// Costs for specific methods by name.
+    pub costs: HashMap<String, u64>,
+}
+
+impl Configuration {
+    /// Creates a new configuration allowing the specified number of requests
+    /// per second, where all methods have a cost of 1.
+    pub fn per_second(rate: NonZeroU64) -> Self {
+        Self {
+            rate,
+            burst: rate.get(),
+            default_cost: 1,
+            costs: HashMap::new(),
+        }
+    }
+
+    /// Sets the cost for the specified method.
+    pub fn with_cost<M>(mut self, method: M, cost: u64) -> Self
+    where
+        M: Method,
+    {
+        self.costs.insert(method.name().into_owned(), cost);
+        self
+    }
+
+    /// Returns the cost of a method by name.
+    fn cost(&self, method: &str) -> u64 {
+        self.costs.get(method).copied().unwrap_or(self.default_cost)
+    }
+}
+
+/// A token bucket.
+///
+/// Reservations that exceed the available budget put the bucket into debt
+/// that future reservations need to wait for, ensuring that delayed calls are
+/// executed in order.
+struct Bucket {
+    budget: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(config: &Configuration, now: Instant) -> Self {
+        Self {
+            budget: config.burst as f64,
+            updated: now,
+        }
+    }
+
+    /// Reserves budget for the specified cost, returning the delay to wait for
+    /// before the budget is available.
+    fn reserve(&mut self, config: &Configuration, cost: u64, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated);
+        self.budget = (self.budget + elapsed.as_secs_f64() * config.rate.get() as f64)
+            .min(config.burst as f64);
+        self.updated = now;
+
+        self.budget -= cost as f64;
+        if self.budget >= 0. {
+            return Duration::ZERO;
+        }
+        Duration::try_from_secs_f64(-self.budget / config.rate.get() as f64)
+            .unwrap_or(Duration::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth;
+
+    #[test]
+    fn method_costs() {
+        let config =
+            Configuration::per_second(NonZeroU64::new(10).unwrap()).with_cost(eth::GetLogs, 5);
+        assert_eq!(config.cost("eth_getLogs"), 5);
+        assert_eq!(config.cost("eth_blockNumber"), 1);
+    }
+
+    #[test]
+    fn token_bucket() {
+        let config = Configuration {
+            burst: 20,
+            ..Configuration::per_second(NonZeroU64::new(10).unwrap())
+        };
+        let start = Instant::now();
+        let mut bucket = Bucket::new(&config, start);
+
+        // Burst is available immediately.
+        assert_eq!(bucket.reserve(&config, 20, start), Duration::ZERO);
+
+        // Exceeding the budget goes into debt.
+        assert_eq!(
+            bucket.reserve(&config, 5, start),
+            Duration::from_millis(500)
+        );
+        assert_eq!(bucket.reserve(&config, 5, start), Duration::from_secs(1));
+
+        // Budget is replenished over time.
+        assert_eq!(
+            bucket.reserve(&config, 1, start + Duration::from_secs(1)),
+            Duration::from_millis(100),
+        );
+
+        // But never exceeds the burst.
+        assert_eq!(
+            bucket.reserve(&config, 21, start + Duration::from_secs(60)),
+            Duration::from_millis(100),
+        );
+
+        // Calls costing more than the burst are delayed, not rejected.
+        assert_eq!(
+            bucket.reserve(&config, 30, start + Duration::from_secs(120)),
+            Duration::from_secs(1),
+        );
+    }
+}
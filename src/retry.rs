@@ -0,0 +1,444 @@
+//! Retrying JSON RPC client.
+//!
+//! This module provides a client that wraps a transport and retries failed
+//! roundtrips with exponential backoff. A [`Policy`] decides which failures are
+//! transient and worth retrying (such as connection errors or rate limiting),
+//! and which are permanent (such as reverts or invalid parameters).
+
+use crate::{
+    jsonrpc::{self, ErrorCode, Request, Response},
+    transport::{BlockingTransport, Transport},
+};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher as _, Hasher as _},
+    thread,
+    time::Duration,
+};
+
+/// A JSON RPC client that retries failed roundtrips.
+pub struct Retry<T, P = Transient> {
+    inner: T,
+    policy: P,
+    config: Configuration,
+}
+
+impl<T> Retry<T> {
+    /// Creates a new retrying client around the specified transport, with the
+    /// default policy of retrying transient errors.
+    pub fn new(inner: T, config: Configuration) -> Self {
+        Self::with_policy(inner, Transient, config)
+    }
+}
+
+impl<T, P> Retry<T, P> {
+    /// Creates a new retrying client around the specified transport with a
+    /// custom retry policy.
+    pub fn with_policy(inner: T, policy: P, config: Configuration) -> Self {
+        Self {
+            inner,
+            policy,
+            config,
+        }
+    }
+
+    /// Returns a reference to the inner transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns whether or not the result of a roundtrip should be retried.
+    fn retry<E>(&self, result: &Result<Response, E>) -> bool
+    where
+        P: Policy<E>,
+    {
+        match result {
+            Ok(response) => self.retry_response::<E>(response),
+            Err(err) => self.policy.retry_error(err),
+        }
+    }
+
+    /// Returns whether or not the result of a batch roundtrip should be
+    /// retried.
+    fn retry_batch<E>(&self, result: &Result<Vec<Response>, E>) -> bool
+    where
+        P: Policy<E>,
+    {
+        match result {
+            Ok(responses) => responses
+                .iter()
+                .any(|response| self.retry_response::<E>(response)),
+            Err(err) => self.policy.retry_error(err),
+        }
+    }
+
+    fn retry_response<E>(&self, response: &Response) -> bool
+    where
+        P: Policy<E>,
+    {
+        response
+            .result
+            .as_ref()
+            .is_err_and(|err| Policy::<E>::retry_response(&self.policy, err))
+    }
+}
+
+impl<T, P> Transport for Retry<T, P>
+where
+    T: Transport,
+    P: Policy<T::Error> + Send + Sync,
+{
+    type Error = T::Error;
+
+    async fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        let mut attempt = 0;
+        loop {
+            let result = self.inner.roundtrip(request.clone()).await;
+            if attempt >= self.config.max_retries || !self.retry(&result) {
+                return result;
+            }
+            drop(result);
+
+            tokio::time::sleep(self.config.delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Sends a batch of JSON RPC requests.
+    ///
+    /// Note that the whole batch is retried if any of its responses is a
+    /// retryable JSON RPC error.
+    async fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        let mut attempt = 0;
+        loop {
+            let result = self.inner.roundtrip_batch(requests.clone()).await;
+            if attempt >= self.config.max_retries || !self.retry_batch(&result) {
+                return result;
+            }
+            drop(result);
+
+            tokio::time::sleep(self.config.delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+impl<T, P> BlockingTransport for Retry<T, P>
+where
+    T: BlockingTransport,
+    P: Policy<T::Error>,
+{
+    type Error = T::Error;
+
+    fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        let mut attempt = 0;
+        loop {
+            let result = self.inner.roundtrip(request.clone());
+            if attempt >= self.config.max_retries || !self.retry(&result) {
+                return result;
+            }
+
+            thread::sleep(self.config.delay(attempt));
+            attempt += 1;
+        }
+    }
+
+    /// Sends a batch of JSON RPC requests.
+    ///
+    /// Note that the whole batch is retried if any of its responses is a
+    /// retryable JSON RPC error.
+    fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        let mut attempt = 0;
+        loop {
+            let result = self.inner.roundtrip_batch(requests.clone());
+            if attempt >= self.config.max_retries || !self.retry_batch(&result) {
+                return result;
+            }
+
+            thread::sleep(self.config.delay(attempt));
+            attempt += 1;
+        }
+    }
+}
+
+/// Retry configuration.
+#[derive(Clone, Debug)]
+pub struct Configuration {
+    /// The maximum number of times a roundtrip is retried.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub initial_delay: Duration,
+    /// The maximum delay between retries.
+    pub max_delay: Duration,
+    /// The factor by which the delay grows after each retry.
+    pub multiplier: f64,
+    /// The fraction of each delay, between `0.0` and `1.0`, that is randomized
+    /// in order to avoid many clients retrying in lockstep.
+    pub jitter: f64,
+}
+
+impl Configuration {
+    /// Returns the delay to wait before the specified retry attempt (starting
+    /// from 0).
+    fn delay(&self, attempt: u32) -> Duration {
+        let delay = (self.initial_delay.as_secs_f64()
+            * self.multiplier.powi(attempt.try_into().unwrap_or(i32::MAX)))
+        .min(self.max_delay.as_secs_f64());
+        let jitter = self.jitter.clamp(0., 1.) * random();
+        Duration::try_from_secs_f64(delay * (1. - jitter)).unwrap_or(self.max_delay)
+    }
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2.,
+            jitter: 0.5,
+        }
+    }
+}
+
+/// Returns a random number in the range `[0, 1)`.
+///
+/// This uses the standard library's randomly seeded hasher, which is good
+/// enough for jitter and avoids an additional dependency.
+fn random() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+/// A policy for deciding whether or not failures should be retried, where `E`
+/// is the transport error type.
+pub trait Policy<E> {
+    /// Returns whether or not a failed roundtrip should be retried.
+    fn retry_error(&self, err: &E) -> bool;
+
+    /// Returns whether or not a JSON RPC error response should be retried.
+    fn retry_response(&self, err: &jsonrpc::Error) -> bool;
+}
+
+/// The default retry policy, which retries [`Retryable`] errors.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Transient;
+
+impl<E> Policy<E> for Transient
+where
+    E: Retryable,
+{
+    fn retry_error(&self, err: &E) -> bool {
+        err.is_retryable()
+    }
+
+    fn retry_response(&self, err: &jsonrpc::Error) -> bool {
+        err.is_retryable()
+    }
+}
+
+/// Classification of errors into transient errors that can be retried and
+/// permanent errors that can't.
+pub trait Retryable {
+    /// Returns `true` if the error is transient and the request can be
+    /// retried.
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for ErrorCode {
+    /// Rate limiting error codes are retryable. This includes the standard
+    /// "limit exceeded" code from EIP-1474 as well as the HTTP 429 status that
+    /// some node providers use as an error code.
+    fn is_retryable(&self) -> bool {
+        matches!(i32::from(*self), -32005 | 429)
+    }
+}
+
+impl Retryable for jsonrpc::Error {
+    fn is_retryable(&self) -> bool {
+        self.code.is_retryable()
+    }
+}
+
+#[cfg(feature = "curl")]
+impl Retryable for crate::curl::Error {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Http(err) => {
+                err.is_couldnt_resolve_host()
+                    || err.is_couldnt_connect()
+                    || err.is_operation_timedout()
+                    || err.is_send_error()
+                    || err.is_recv_error()
+                    || err.is_got_nothing()
+                    || err.is_again()
+            }
+            Self::Status(status, _) => *status == 429 || (500..600).contains(status),
+            Self::Rpc(err) => err.is_retryable(),
+            Self::Json(_) | Self::Batch(_) => false,
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl Retryable for crate::http::Error {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Http(err) => {
+                err.is_connect() || err.is_timeout() || err.is_request() || err.is_body()
+            }
+            Self::Status(status, _) => {
+                *status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+            Self::Rpc(err) => err.is_retryable(),
+            Self::Json(_) | Self::Batch(_) => false,
+        }
+    }
+}
+
+#[cfg(all(feature = "ipc", unix))]
+impl Retryable for crate::ipc::Error {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Io(_) | Self::Closed => true,
+            Self::Rpc(err) => err.is_retryable(),
+            Self::Json(_) | Self::DuplicateId(_) | Self::Batch(_) => false,
+        }
+    }
+}
+
+#[cfg(feature = "ws")]
+impl Retryable for crate::ws::Error {
+    fn is_retryable(&self) -> bool {
+        use crate::ws::tungstenite::Error as WsError;
+        match self {
+            Self::WebSocket(err) => match err {
+                WsError::ConnectionClosed | WsError::AlreadyClosed | WsError::Io(_) => true,
+                WsError::Http(response) => {
+                    let status = response.status();
+                    status.as_u16() == 429 || status.is_server_error()
+                }
+                _ => false,
+            },
+            Self::Closed => true,
+            Self::Rpc(err) => err.is_retryable(),
+            Self::Json(_) | Self::DuplicateId(_) | Self::Batch(_) => false,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl Retryable for crate::mock::Error {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Failure(_) => true,
+            Self::Rpc(err) => err.is_retryable(),
+            Self::Json(_) | Self::Unexpected(_) | Self::Batch(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth,
+        mock::{self, Mock},
+        transport::BlockingTransportExt as _,
+        types::Empty,
+    };
+
+    fn error(code: ErrorCode) -> jsonrpc::Error {
+        jsonrpc::Error {
+            code,
+            message: String::new(),
+            data: Default::default(),
+        }
+    }
+
+    fn config() -> Configuration {
+        Configuration {
+            initial_delay: Duration::ZERO,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn retries_transient_errors() {
+        let mut mock = Mock::new();
+        mock.expect(eth::BlockNumber).fails("connection").times(1);
+        mock.expect(eth::BlockNumber)
+            .returns_error(error(ErrorCode::from(-32005)))
+            .times(1);
+        mock.expect(eth::BlockNumber).returns(42);
+
+        let client = Retry::new(mock, config());
+        assert_eq!(client.call_np(eth::BlockNumber).unwrap(), 42);
+        assert_eq!(client.inner().requests().len(), 3);
+    }
+
+    #[test]
+    fn does_not_retry_permanent_errors() {
+        let mut mock = Mock::new();
+        mock.expect(eth::BlockNumber)
+            .returns_error(error(ErrorCode::InvalidParams))
+            .times(1);
+        mock.expect(eth::BlockNumber).returns(42);
+
+        let client = Retry::new(mock, config());
+        assert!(matches!(
+            client.call(eth::BlockNumber, Empty),
+            Err(mock::Error::Rpc(jsonrpc::Error {
+                code: ErrorCode::InvalidParams,
+                ..
+            })),
+        ));
+        assert_eq!(client.inner().requests().len(), 1);
+    }
+
+    #[test]
+    fn gives_up_after_max_retries() {
+        let mut mock = Mock::new();
+        mock.expect(eth::BlockNumber).fails("connection").times(2);
+        mock.expect(eth::BlockNumber).returns(42);
+
+        let client = Retry::new(
+            mock,
+            Configuration {
+                max_retries: 1,
+                ..config()
+            },
+        );
+        assert!(matches!(
+            client.call_np(eth::BlockNumber),
+            Err(mock::Error::Failure(_)),
+        ));
+        assert_eq!(client.inner().requests().len(), 2);
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let config = Configuration {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2.,
+            jitter: 0.,
+            ..Default::default()
+        };
+        assert_eq!(
+            (0..5)
+                .map(|attempt| config.delay(attempt))
+                .collect::<Vec<_>>(),
+            [1, 2, 4, 5, 5].map(Duration::from_secs),
+        );
+
+        let config = Configuration {
+            jitter: 0.5,
+            ..config
+        };
+        for _ in 0..100 {
+            let delay = config.delay(1);
+            assert!(delay > Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+}
@@ -328,3 +328,27 @@ pub mod option_num {
         Ok(Option::<Quantity<T>>::deserialize(deserializer)?.map(|value| value.0))
     }
 }
+
+/// Serialize arbitrary JSON [`Value`](crate::jsonrpc::Value) method results.
+/// This is needed because its inherent `serialize` and `deserialize` methods
+/// shadow the `serde` trait methods.
+pub mod value {
+    use super::*;
+    use crate::jsonrpc::Value;
+
+    #[doc(hidden)]
+    pub fn serialize<S>(value: &Value, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.0.serialize(serializer)
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        serde_json::Value::deserialize(deserializer).map(Value)
+    }
+}
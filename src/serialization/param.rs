@@ -39,8 +39,9 @@ pub mod fee_history {
     }
 }
 
-/// Serialize a `(_, u64)` parameter pair where the number is a hex quantity.
-pub mod get_transaction_by_block_and_index {
+/// Serialize a `(block, index)` parameter pair where the index is a hex
+/// quantity.
+pub mod block_and_index {
     use super::*;
     use crate::serialization::num::Quantity;
 
@@ -104,7 +105,7 @@ pub mod call_like {
 }
 
 /// Serialize a single bytes parameter as a one-item JSON RPC params array.
-pub mod eth_send_raw_transaction {
+pub mod bytes {
     use super::*;
     use crate::serialization::bytes::{decode, encode};
     use std::{borrow::Cow, str};
@@ -129,7 +130,7 @@ pub mod eth_send_raw_transaction {
 }
 
 /// Serialize `(address, bytes)` as JSON RPC params.
-pub mod eth_sign {
+pub mod address_bytes {
     use super::*;
     use crate::serialization::bytes::{decode, encode};
     use ethprim::Address;
@@ -153,3 +154,178 @@ pub mod eth_sign {
         Ok((address, decode(&hex)?))
     }
 }
+
+/// Serialize `(address, u64)` as JSON RPC params where the number is a hex
+/// quantity.
+pub mod address_quantity {
+    use super::*;
+    use crate::serialization::num::Quantity;
+    use ethprim::Address;
+
+    #[doc(hidden)]
+    pub fn serialize<S>((address, value): &(Address, u64), serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (address, Quantity::from_ref(value)).serialize(serializer)
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<(Address, u64), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (address, Quantity(value)) = Deserialize::deserialize(deserializer)?;
+        Ok((address, value))
+    }
+}
+
+/// Serialize a single optional parameter, omitting it when unspecified.
+pub mod optional {
+    use super::*;
+
+    #[doc(hidden)]
+    pub fn serialize<T, S>((value,): &(Option<T>,), serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => (value,).serialize(serializer),
+            None => <[(); 0]>::default().serialize(serializer),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<(Option<T>,), D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        if values.len() > 1 {
+            return Err(serde::de::Error::invalid_length(
+                values.len(),
+                &"at most 1 parameter",
+            ));
+        }
+        Ok((values.into_iter().next(),))
+    }
+}
+
+/// Serialize `engine_newPayloadV4` parameters.
+pub mod engine_new_payload_v4 {
+    use super::*;
+    use crate::{
+        serialization::bytes::{decode, encode},
+        types::ExecutionPayload,
+    };
+    use ethprim::Digest;
+    use std::{borrow::Cow, str};
+
+    type Params = (ExecutionPayload, Vec<Digest>, Digest, Vec<Vec<u8>>);
+
+    #[doc(hidden)]
+    pub fn serialize<S>(value: &Params, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (payload, versioned_hashes, parent_beacon_block_root, execution_requests) = value;
+        (
+            payload,
+            versioned_hashes,
+            parent_beacon_block_root,
+            execution_requests
+                .iter()
+                .map(|request| encode(request))
+                .collect::<Vec<_>>(),
+        )
+            .serialize(serializer)
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Params, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (payload, versioned_hashes, parent_beacon_block_root, execution_requests): (
+            ExecutionPayload,
+            Vec<Digest>,
+            Digest,
+            Vec<Cow<str>>,
+        ) = Deserialize::deserialize(deserializer)?;
+        Ok((
+            payload,
+            versioned_hashes,
+            parent_beacon_block_root,
+            execution_requests
+                .iter()
+                .map(|request| decode(request))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+/// Serialize a single number parameter as a one-item JSON RPC params array
+/// with a hex quantity.
+pub mod quantity {
+    use super::*;
+    use crate::serialization::num::Quantity;
+
+    #[doc(hidden)]
+    pub fn serialize<S>((value,): &(u64,), serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (Quantity::from_ref(value),).serialize(serializer)
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<(u64,), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (Quantity(value),) = Deserialize::deserialize(deserializer)?;
+        Ok((value,))
+    }
+}
+
+/// Serialize `anvil_mine` and `hardhat_mine` parameters, omitting trailing
+/// unspecified values.
+pub mod mine {
+    use super::*;
+    use crate::serialization::num::Quantity;
+
+    #[doc(hidden)]
+    pub fn serialize<S>(
+        value: &(Option<u64>, Option<u64>),
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let blocks = value.0.as_ref().map(Quantity::from_ref);
+        let interval = value.1.as_ref().map(Quantity::from_ref);
+        match (blocks, interval) {
+            (_, Some(interval)) => (blocks, interval).serialize(serializer),
+            (Some(blocks), None) => (blocks,).serialize(serializer),
+            (None, None) => <[(); 0]>::default().serialize(serializer),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<(Option<u64>, Option<u64>), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let values = Vec::<Option<Quantity<u64>>>::deserialize(deserializer)?;
+        if values.len() > 2 {
+            return Err(serde::de::Error::invalid_length(
+                values.len(),
+                &"at most 2 parameters",
+            ));
+        }
+        let mut values = values.into_iter().map(|value| value.map(|value| value.0));
+        Ok((values.next().flatten(), values.next().flatten()))
+    }
+}
@@ -0,0 +1,261 @@
+//! JSON RPC HTTP server.
+//!
+//! This module exposes a [`Handler`] over HTTP on a local socket. This is
+//! mostly useful for running lightweight fake nodes in integration tests. JSON
+//! RPC messages are accepted as `POST` request bodies, and responses are sent
+//! back as JSON. Messages that only contain notifications are answered with an
+//! empty `204 No Content` response, and bodies larger than [`MAX_BODY_SIZE`]
+//! are rejected with a `413 Payload Too Large` response.
+
+use super::Handler;
+use http_body_util::{BodyExt as _, Full, LengthLimitError, Limited};
+use hyper::{
+    Method, Request, Response, StatusCode,
+    body::{Bytes, Incoming},
+    header,
+    server::conn::http1,
+    service::service_fn,
+};
+use hyper_util::rt::TokioIo;
+use std::{convert::Infallible, io, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    net::{TcpListener, ToSocketAddrs},
+    sync::oneshot,
+    task::{JoinHandle, JoinSet},
+};
+
+/// The maximum size of a request body in bytes.
+pub const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// The amount of time to wait before accepting connections again after an
+/// error. Errors such as running out of file descriptors persist for some time,
+/// so retrying immediately would just spin.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// A running JSON RPC HTTP server.
+///
+/// The server is stopped when it is dropped.
+pub struct Server {
+    addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+}
+
+impl Server {
+    /// Starts a new JSON RPC HTTP server for the specified handler, listening
+    /// on the specified address.
+    ///
+    /// Use port `0` to listen on an arbitrary available port, which can then
+    /// be retrieved with [`Server::addr`]. Note that this spawns a background
+    /// task that accepts connections, and must be called from within a Tokio
+    /// runtime.
+    pub async fn bind<H>(addr: impl ToSocketAddrs, handler: H) -> io::Result<Self>
+    where
+        H: Handler + 'static,
+    {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let (shutdown, signal) = oneshot::channel();
+        let task = tokio::spawn(accept(listener, Arc::new(handler), signal));
+        Ok(Self {
+            addr,
+            shutdown: Some(shutdown),
+            task,
+        })
+    }
+
+    /// Returns the local address that the server is listening on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the HTTP URL of the server.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Stops the server, waiting for it to stop accepting connections.
+    /// Connections that are still open are closed.
+    pub async fn stop(mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Accepts connections until the shutdown signal is received.
+async fn accept<H>(listener: TcpListener, handler: Arc<H>, mut signal: oneshot::Receiver<()>)
+where
+    H: Handler + 'static,
+{
+    let mut connections = JoinSet::new();
+    loop {
+        tokio::select! {
+            _ = &mut signal => break,
+            Some(_) = connections.join_next() => (),
+            accepted = listener.accept() => {
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(err) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %err, "failed to accept connection");
+                        #[cfg(not(feature = "tracing"))]
+                        let _ = err;
+                        tokio::time::sleep(ACCEPT_BACKOFF).await;
+                        continue;
+                    }
+                };
+                let handler = handler.clone();
+                connections.spawn(async move {
+                    let service = service_fn(move |request| {
+                        let handler = handler.clone();
+                        async move { Ok::<_, Infallible>(serve(&*handler, request).await) }
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        }
+    }
+}
+
+/// Serves a single HTTP request.
+async fn serve<H>(handler: &H, request: Request<Incoming>) -> Response<Full<Bytes>>
+where
+    H: Handler + ?Sized,
+{
+    if request.method() != Method::POST {
+        return status(StatusCode::METHOD_NOT_ALLOWED);
+    }
+    let body = match Limited::new(request.into_body(), MAX_BODY_SIZE)
+        .collect()
+        .await
+    {
+        Ok(body) => body,
+        Err(err) if err.is::<LengthLimitError>() => {
+            return status(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+        Err(_) => return status(StatusCode::BAD_REQUEST),
+    };
+
+    // Bodies that aren't valid UTF-8 fail to parse as JSON, and are answered
+    // with a JSON RPC parse error.
+    let body = body.to_bytes();
+    match handler.handle_json(&String::from_utf8_lossy(&body)) {
+        Some(response) => Response::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(response)))
+            .expect("valid HTTP response"),
+        None => status(StatusCode::NO_CONTENT),
+    }
+}
+
+/// Returns an empty response with the specified status code.
+fn status(code: StatusCode) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(code)
+        .body(Full::default())
+        .expect("valid HTTP response")
+}
+
+#[cfg(all(test, feature = "http"))]
+mod tests {
+    use super::*;
+    use crate::{eth, http::Client, jsonrpc, server::Router, types::Empty};
+    use serde_json::json;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn serves_json_rpc_over_http() {
+        runtime().block_on(async {
+            let mut router = Router::new();
+            router
+                .register(eth::BlockNumber, |_| Ok(42))
+                .register(eth::ChainId, |_| Ok(1));
+            let server = Server::bind("127.0.0.1:0", router).await.unwrap();
+
+            let client = Client::new(server.url().parse().unwrap());
+            assert_eq!(client.call_np(eth::BlockNumber).await.unwrap(), 42);
+            assert_eq!(
+                client
+                    .batch(((eth::ChainId, Empty), (eth::BlockNumber, Empty)))
+                    .await
+                    .unwrap(),
+                (1, 42),
+            );
+            assert!(matches!(
+                client.call_np(eth::GasPrice).await,
+                Err(crate::http::Error::Rpc(jsonrpc::Error {
+                    code: jsonrpc::ErrorCode::MethodNotFound,
+                    ..
+                })),
+            ));
+
+            let http = reqwest::Client::new();
+            let post = |body: &'static str| http.post(server.url()).body(body).send();
+
+            let response = post(r#"{"jsonrpc":"2.0","method":"eth_chainId"}"#)
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+            for (body, code, id) in [
+                ("{", -32700, json!(null)),
+                ("[]", -32600, json!(null)),
+                (r#"{"id":1}"#, -32600, json!(1)),
+            ] {
+                let response = post(body)
+                    .await
+                    .unwrap()
+                    .json::<serde_json::Value>()
+                    .await
+                    .unwrap();
+                assert_eq!(response["error"]["code"], json!(code));
+                assert_eq!(response.get("id"), Some(&id));
+            }
+
+            let response = http
+                .post(server.url())
+                .body(" ".repeat(MAX_BODY_SIZE + 1))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+            server.stop().await;
+        });
+    }
+
+    #[test]
+    fn malformed_body_has_null_id() {
+        runtime().block_on(async {
+            let server = Server::bind("127.0.0.1:0", Router::new()).await.unwrap();
+
+            let response = reqwest::Client::new()
+                .post(server.url())
+                .body("not json")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.text().await.unwrap();
+            assert!(body.contains(r#""id":null"#), "{body}");
+
+            server.stop().await;
+        });
+    }
+}
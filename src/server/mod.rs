@@ -0,0 +1,451 @@
+//! Server-side JSON RPC request handling.
+//!
+//! This module provides a [`Router`] for dispatching JSON RPC requests to
+//! handlers registered for typed [`Method`]s. Parameters and results are
+//! (de)serialized with the same method definitions that are used by clients,
+//! which makes it easy to build mock nodes and proxies.
+//!
+//! Any [`Handler`], such as a [`Router`] or a closure over [`Request`]s, can
+//! also be exposed over HTTP with the `http-server` feature.
+
+#[cfg(feature = "http-server")]
+pub mod http;
+
+use crate::{
+    jsonrpc::{self, ErrorCode, Id, JsonError, Notification, Request, Response, Value, batch},
+    method::Method,
+    transport::{BlockingTransport, Transport},
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// A JSON RPC request handler.
+pub trait Handler: Send + Sync {
+    /// Handles a JSON RPC request.
+    fn handle(&self, request: Request) -> Response;
+
+    /// Handles a JSON RPC notification.
+    ///
+    /// Notifications do not have a response, so they are ignored by default.
+    fn notify(&self, notification: Notification) {
+        let _ = notification;
+    }
+
+    /// Handles a raw JSON RPC message, which can either be a single request or
+    /// notification, or a batch of them.
+    ///
+    /// Returns the serialized response, or `None` if the message only
+    /// contained notifications. Malformed messages are answered with
+    /// [`ErrorCode::ParseError`] and [`ErrorCode::InvalidRequest`] errors as
+    /// specified by JSON RPC.
+    ///
+    /// Raw messages may use any string, number or `null` request ID. Requests
+    /// are passed to [`Handler::handle`] with a new [`Id`], and responses are
+    /// sent back with the ID from the original message.
+    fn handle_json(&self, message: &str) -> Option<String> {
+        let response = match serde_json::from_str::<serde_json::Value>(message) {
+            Ok(serde_json::Value::Array(messages)) if messages.is_empty() => {
+                reply(invalid_request(), serde_json::Value::Null)
+            }
+            Ok(serde_json::Value::Array(messages)) => {
+                let responses = messages
+                    .into_iter()
+                    .filter_map(|message| handle_message(self, message))
+                    .collect::<Vec<_>>();
+                if responses.is_empty() {
+                    return None;
+                }
+                serde_json::Value::Array(responses)
+            }
+            Ok(message) => handle_message(self, message)?,
+            Err(err) => reply(
+                Response {
+                    jsonrpc: jsonrpc::Version::V2,
+                    result: Err(error(ErrorCode::ParseError, err)),
+                    id: None,
+                },
+                serde_json::Value::Null,
+            ),
+        };
+        Some(response.to_string())
+    }
+}
+
+impl<F> Handler for F
+where
+    F: Fn(Request) -> Response + Send + Sync,
+{
+    fn handle(&self, request: Request) -> Response {
+        self(request)
+    }
+}
+
+/// A type-erased method handler.
+type MethodHandler = Box<dyn Fn(Value) -> Result<Value, jsonrpc::Error> + Send + Sync>;
+
+/// A JSON RPC router that dispatches requests to registered method handlers.
+#[derive(Default)]
+pub struct Router {
+    handlers: HashMap<String, MethodHandler>,
+}
+
+impl Router {
+    /// Creates a new router without any registered methods.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a handler for the specified method, replacing any previously
+    /// registered handler for a method with the same name.
+    ///
+    /// Requests with parameters that can't be decoded are answered with an
+    /// [`ErrorCode::InvalidParams`] error without calling the handler.
+    pub fn register<M, F>(&mut self, method: M, handler: F) -> &mut Self
+    where
+        M: Method + 'static,
+        F: Fn(M::Params) -> Result<M::Result, jsonrpc::Error> + Send + Sync + 'static,
+    {
+        self.handlers.insert(
+            method.name().into_owned(),
+            Box::new(move |params| {
+                let params = params
+                    .params::<M>()
+                    .map_err(|err| error(ErrorCode::InvalidParams, err))?;
+                let result = handler(params)?;
+                Value::for_result::<M>(result).map_err(|err| error(ErrorCode::InternalError, err))
+            }),
+        );
+        self
+    }
+
+    /// Returns whether or not a handler is registered for the specified method
+    /// name.
+    pub fn contains(&self, method: &str) -> bool {
+        self.handlers.contains_key(method)
+    }
+
+    /// Handles a JSON RPC request.
+    pub fn handle(&self, request: Request) -> Response {
+        Response {
+            jsonrpc: jsonrpc::Version::V2,
+            result: self.dispatch(request.method.as_str(), request.params),
+            id: Some(request.id),
+        }
+    }
+
+    /// Handles a batch of JSON RPC requests.
+    pub fn handle_batch(&self, requests: Vec<Request>) -> Vec<Response> {
+        requests
+            .into_iter()
+            .map(|request| self.handle(request))
+            .collect()
+    }
+
+    /// Handles a JSON RPC notification. Notifications do not produce a
+    /// response, so the result of the handler is discarded.
+    pub fn notify(&self, notification: Notification) {
+        let _ = self.dispatch(notification.method.as_str(), notification.params);
+    }
+
+    fn dispatch(&self, method: &str, params: Value) -> Result<Value, jsonrpc::Error> {
+        let handler = self.handlers.get(method).ok_or_else(|| jsonrpc::Error {
+            code: ErrorCode::MethodNotFound,
+            message: format!("method '{method}' not found"),
+            data: Value::default(),
+        })?;
+        handler(params)
+    }
+}
+
+impl Handler for Router {
+    fn handle(&self, request: Request) -> Response {
+        Router::handle(self, request)
+    }
+
+    fn notify(&self, notification: Notification) {
+        Router::notify(self, notification)
+    }
+}
+
+/// A router can be used directly as an in-process transport, which is useful
+/// for testing clients against a mock node.
+impl Transport for Router {
+    type Error = Error;
+
+    async fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        Ok(self.handle(request))
+    }
+
+    async fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        Ok(self.handle_batch(requests))
+    }
+}
+
+impl BlockingTransport for Router {
+    type Error = Error;
+
+    fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        Ok(self.handle(request))
+    }
+
+    fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        Ok(self.handle_batch(requests))
+    }
+}
+
+/// Handles a single message from a raw JSON RPC request.
+fn handle_message<H>(handler: &H, message: serde_json::Value) -> Option<serde_json::Value>
+where
+    H: Handler + ?Sized,
+{
+    /// An incoming message, where `params` may be omitted. The `id` is read
+    /// from the raw message, as it is only specified for requests and can be
+    /// any string, number or `null`.
+    #[derive(Deserialize)]
+    struct Incoming {
+        jsonrpc: jsonrpc::Version,
+        method: jsonrpc::Method,
+        params: Option<Value>,
+    }
+
+    let id = message.get("id").cloned();
+    let valid_id = id.as_ref().is_none_or(|id| {
+        matches!(
+            id,
+            serde_json::Value::Null | serde_json::Value::Number(_) | serde_json::Value::String(_),
+        )
+    });
+    let (true, Ok(incoming)) = (valid_id, Incoming::deserialize(message)) else {
+        let id = id.filter(|_| valid_id).unwrap_or(serde_json::Value::Null);
+        return Some(reply(invalid_request(), id));
+    };
+
+    let params = incoming
+        .params
+        .unwrap_or_else(|| Value(serde_json::Value::Array(Vec::new())));
+    match id {
+        Some(id) => {
+            let response = handler.handle(Request {
+                jsonrpc: incoming.jsonrpc,
+                method: incoming.method,
+                params,
+                id: Id::next(),
+            });
+            Some(reply(response, id))
+        }
+        None => {
+            handler.notify(Notification {
+                jsonrpc: incoming.jsonrpc,
+                method: incoming.method,
+                params,
+            });
+            None
+        }
+    }
+}
+
+/// Serializes a response to a raw JSON RPC message with the raw request ID.
+///
+/// Errors for messages whose ID can't be determined, such as parse errors,
+/// must be sent with a `null` ID as specified by JSON RPC.
+fn reply(response: Response, id: serde_json::Value) -> serde_json::Value {
+    let mut reply = serde_json::to_value(response).expect("JSON RPC responses always serialize");
+    reply["id"] = id;
+    reply
+}
+
+/// Creates a JSON RPC error with the specified code.
+fn error(code: ErrorCode, err: impl ToString) -> jsonrpc::Error {
+    jsonrpc::Error {
+        code,
+        message: err.to_string(),
+        data: Value::default(),
+    }
+}
+
+/// Creates an invalid request response.
+fn invalid_request() -> Response {
+    Response {
+        jsonrpc: jsonrpc::Version::V2,
+        result: Err(error(ErrorCode::InvalidRequest, "invalid request")),
+        id: None,
+    }
+}
+
+/// An error using a [`Router`] as a transport.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON error: {0}")]
+    Json(#[from] JsonError),
+    #[error(transparent)]
+    Rpc(#[from] jsonrpc::Error),
+    #[error(transparent)]
+    Batch(#[from] batch::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth,
+        transport::BlockingTransportExt as _,
+        types::{BlockTag, Empty},
+    };
+    use ethprim::{address, uint};
+    use serde_json::json;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    fn router() -> Router {
+        let mut router = Router::new();
+        router
+            .register(eth::BlockNumber, |_| Ok(42))
+            .register(eth::ChainId, |_| Ok(1))
+            .register(eth::GetBalance, |(_, block)| match block {
+                crate::types::BlockId::Tag(BlockTag::Latest) => Ok(uint!("1000")),
+                _ => Err(jsonrpc::Error::custom("unsupported block")),
+            });
+        router
+    }
+
+    #[test]
+    fn typed_calls() {
+        let router = router();
+        let account = address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41");
+
+        assert_eq!(router.call_np(eth::BlockNumber).unwrap(), 42);
+        assert_eq!(
+            router
+                .call(eth::GetBalance, (account, BlockTag::Latest.into()))
+                .unwrap(),
+            uint!("1000"),
+        );
+        assert!(matches!(
+            router.call(eth::GetBalance, (account, BlockTag::Safe.into())),
+            Err(Error::Rpc(jsonrpc::Error {
+                code: ErrorCode::ServerError(-32000),
+                ..
+            })),
+        ));
+        assert!(matches!(
+            router.call_np(eth::GasPrice),
+            Err(Error::Rpc(jsonrpc::Error {
+                code: ErrorCode::MethodNotFound,
+                ..
+            })),
+        ));
+        assert_eq!(
+            router
+                .batch(((eth::ChainId, Empty), (eth::BlockNumber, Empty)))
+                .unwrap(),
+            (1, 42),
+        );
+    }
+
+    #[test]
+    fn raw_messages() {
+        let router = router();
+        let handle = |message: serde_json::Value| {
+            router
+                .handle_json(&message.to_string())
+                .map(|response| serde_json::from_str::<serde_json::Value>(&response).unwrap())
+        };
+
+        assert_eq!(
+            handle(json!({ "jsonrpc": "2.0", "method": "eth_blockNumber", "id": 1 })),
+            Some(json!({ "jsonrpc": "2.0", "result": "0x2a", "id": 1 })),
+        );
+        assert_eq!(
+            handle(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_getBalance",
+                "params": ["not an address"],
+                "id": 2,
+            }))
+            .unwrap()["error"]["code"],
+            json!(-32602),
+        );
+        assert_eq!(
+            handle(json!([
+                { "jsonrpc": "2.0", "method": "eth_chainId", "params": [], "id": 3 },
+                { "jsonrpc": "2.0", "method": "eth_chainId", "params": [] },
+                { "jsonrpc": "2.0", "method": "eth_foo", "params": [], "id": 4 },
+                { "foo": "bar" },
+            ])),
+            Some(json!([
+                { "jsonrpc": "2.0", "result": "0x1", "id": 3 },
+                {
+                    "jsonrpc": "2.0",
+                    "error": { "code": -32601, "message": "method 'eth_foo' not found", "data": null },
+                    "id": 4,
+                },
+                {
+                    "jsonrpc": "2.0",
+                    "error": { "code": -32600, "message": "invalid request", "data": null },
+                    "id": null,
+                },
+            ])),
+        );
+        assert_eq!(
+            handle(json!([
+                { "jsonrpc": "2.0", "method": "eth_chainId", "id": "a" },
+                { "jsonrpc": "2.0", "method": "eth_chainId", "id": 4_294_967_296_u64 },
+                { "jsonrpc": "2.0", "method": "eth_chainId", "id": null },
+            ])),
+            Some(json!([
+                { "jsonrpc": "2.0", "result": "0x1", "id": "a" },
+                { "jsonrpc": "2.0", "result": "0x1", "id": 4_294_967_296_u64 },
+                { "jsonrpc": "2.0", "result": "0x1", "id": null },
+            ])),
+        );
+        assert_eq!(
+            handle(json!({ "jsonrpc": "2.0", "method": "eth_chainId", "id": [1] })),
+            Some(json!({
+                "jsonrpc": "2.0",
+                "error": { "code": -32600, "message": "invalid request", "data": null },
+                "id": null,
+            })),
+        );
+        assert_eq!(
+            handle(json!([])),
+            Some(json!({
+                "jsonrpc": "2.0",
+                "error": { "code": -32600, "message": "invalid request", "data": null },
+                "id": null,
+            })),
+        );
+        let parse_error = router
+            .handle_json("{")
+            .map(|response| serde_json::from_str::<serde_json::Value>(&response).unwrap())
+            .unwrap();
+        assert_eq!(parse_error["error"]["code"], json!(-32700));
+        assert_eq!(parse_error.get("id"), Some(&json!(null)));
+    }
+
+    #[test]
+    fn notifications() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut router = Router::new();
+        router.register("custom_ping", {
+            let count = count.clone();
+            move |_| {
+                count.fetch_add(1, Ordering::SeqCst);
+                Ok(json!(null))
+            }
+        });
+
+        assert_eq!(
+            router.handle_json(r#"{"jsonrpc":"2.0","method":"custom_ping","params":[]}"#),
+            None,
+        );
+        assert_eq!(
+            router.handle_json(r#"[{"jsonrpc":"2.0","method":"custom_ping"}]"#),
+            None,
+        );
+        router.notify(Notification::new("custom_ping", json!([])).unwrap());
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,8 +1,10 @@
-use crate::{eth, types::*};
+use crate::{
+    admin, anvil, debug, engine, eth, evm, hardhat, jsonrpc, net, trace, txpool, types::*, web3,
+};
 use ethprim::{address, digest, uint};
 use hex_literal::hex;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 macro_rules! assert_method_serialization {
     ($m:ty {
@@ -345,6 +347,27 @@ fn eth_gas_price() {
     });
 }
 
+#[test]
+fn eth_get_account() {
+    assert_method_serialization!(eth::GetAccount {
+        (
+            address!("0xe5cB067E90D5Cd1F8052B83562Ae670bA4A211a8"),
+            BlockId::Tag(BlockTag::Latest),
+        ) => json!(["0xe5cB067E90D5Cd1F8052B83562Ae670bA4A211a8", "latest"]);
+        Some(Account {
+            balance: uint!("0x1bc16d674ec80000"),
+            code_hash: digest!("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+            nonce: 0x2a,
+            storage_root: digest!("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+        }) => json!({
+            "balance": "0x1bc16d674ec80000",
+            "codeHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            "nonce": "0x2a",
+            "storageRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+        });
+    });
+}
+
 #[test]
 fn eth_get_balance() {
     assert_method_serialization!(eth::GetBalance {
@@ -1248,6 +1271,79 @@ fn eth_get_proof() {
     });
 }
 
+#[test]
+fn eth_get_header_by_hash() {
+    assert_method_serialization!(eth::GetHeaderByHash {
+        (digest!("0xd5f1812548be429cbdc6376b29611fc49e06f1359758c4ceaaa3b393e2239f9c"),)
+            => json!(["0xd5f1812548be429cbdc6376b29611fc49e06f1359758c4ceaaa3b393e2239f9c"]);
+        None => json!(null);
+    });
+}
+
+#[test]
+fn eth_get_header_by_number() {
+    assert_method_serialization!(eth::GetHeaderByNumber {
+        (BlockSpec::Number(0x68b3),) => json!(["0x68b3"]);
+        Some(Header {
+            hash: digest!("0xd5f1812548be429cbdc6376b29611fc49e06f1359758c4ceaaa3b393e2239f9c"),
+            parent_hash: digest!("0x1f68ac259155e2f38211ddad0f0a15394d55417b185a93923e2abe71bb7a4d6d"),
+            sha3_uncles: digest!("0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"),
+            miner: address!("0xB42b6c4A95406C78FF892D270AD20b22642e102D"),
+            state_root: digest!("0x91309efa7e42c1f137f31fe9edbe88ae087e6620d0d59031324da3e2f4f93233"),
+            transactions_root: digest!("0x4d0c8e91e16bdff538c03211c5c73632ed054d00a7e210c0eb25146c20048126"),
+            receipts_root: digest!("0x68461ab700003503a305083630a8fb8d14927238f0bc8b6b3d246c0c64f21f4a"),
+            logs_bloom: Bloom([0; 256]),
+            difficulty: uint!("0x0"),
+            number: 0x68b3,
+            gas_limit: 0x47e7c4,
+            gas_used: 0x37993,
+            timestamp: 0x5835c54d,
+            extra_data: hex!("d583010502846765746885676f312e37856c696e7578").to_vec(),
+            mix_hash: digest!("0x24900fb3da77674a861c428429dce0762707ecb6052325bbd9b3c64e74b5af9d"),
+            nonce: BlockNonce(hex!("0000000000000000")),
+            base_fee_per_gas: uint!("0x7"),
+            withdrawals_root: digest!("0x7a4ecf19774d15cf9c15adf0dd8e8a250c128b26c9e2ab2a08d6c9c8ffbd104f"),
+            blob_gas_used: 0x0,
+            excess_blob_gas: 0x0,
+            parent_beacon_block_root: digest!("0x95c4dbd5b19f6fe3cbc3183be85ff4e85ebe75c5b4fc911f1c91e5b7a554a685"),
+            requests_hash: digest!("0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+        }) => json!({
+            "baseFeePerGas": "0x7",
+            "blobGasUsed": "0x0",
+            "difficulty": "0x0",
+            "excessBlobGas": "0x0",
+            "extraData": "0xd583010502846765746885676f312e37856c696e7578",
+            "gasLimit": "0x47e7c4",
+            "gasUsed": "0x37993",
+            "hash": "0xd5f1812548be429cbdc6376b29611fc49e06f1359758c4ceaaa3b393e2239f9c",
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "miner": "0xB42b6c4A95406C78FF892D270AD20b22642e102D",
+            "mixHash": "0x24900fb3da77674a861c428429dce0762707ecb6052325bbd9b3c64e74b5af9d",
+            "nonce": "0x0000000000000000",
+            "number": "0x68b3",
+            "parentBeaconBlockRoot": "0x95c4dbd5b19f6fe3cbc3183be85ff4e85ebe75c5b4fc911f1c91e5b7a554a685",
+            "parentHash": "0x1f68ac259155e2f38211ddad0f0a15394d55417b185a93923e2abe71bb7a4d6d",
+            "receiptsRoot": "0x68461ab700003503a305083630a8fb8d14927238f0bc8b6b3d246c0c64f21f4a",
+            "requestsHash": "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "stateRoot": "0x91309efa7e42c1f137f31fe9edbe88ae087e6620d0d59031324da3e2f4f93233",
+            "timestamp": "0x5835c54d",
+            "transactionsRoot": "0x4d0c8e91e16bdff538c03211c5c73632ed054d00a7e210c0eb25146c20048126",
+            "withdrawalsRoot": "0x7a4ecf19774d15cf9c15adf0dd8e8a250c128b26c9e2ab2a08d6c9c8ffbd104f",
+        });
+    });
+}
+
+#[test]
+fn eth_get_raw_transaction_by_hash() {
+    assert_method_serialization!(eth::GetRawTransactionByHash {
+        (digest!("0xa0807e117a8dd124ab949f460f08c36c72b710188f01609595223b325e58e0fc"),)
+            => json!(["0xa0807e117a8dd124ab949f460f08c36c72b710188f01609595223b325e58e0fc"]);
+        Some(hex!("02f86a0180843b9aca00843b9aca0082520894000000000000000000000000000000000000000080c0").to_vec())
+            => json!("0x02f86a0180843b9aca00843b9aca0082520894000000000000000000000000000000000000000080c0");
+    });
+}
+
 #[test]
 fn eth_get_storage_at() {
     assert_method_serialization!(eth::GetStorageAt {
@@ -1519,6 +1615,99 @@ fn eth_get_transaction_receipt() {
     });
 }
 
+#[test]
+fn eth_get_uncle_by_block_hash_and_index() {
+    assert_method_serialization!(eth::GetUncleByBlockHashAndIndex {
+        (
+            digest!("0xb903239f8543d04b5dc1ba6579132b143087c68db1b2168786408fcbce568238"),
+            0x1,
+        ) => json!([
+            "0xb903239f8543d04b5dc1ba6579132b143087c68db1b2168786408fcbce568238",
+            "0x1",
+        ]);
+        None => json!(null);
+    });
+}
+
+#[test]
+fn eth_get_uncle_by_block_number_and_index() {
+    assert_method_serialization!(eth::GetUncleByBlockNumberAndIndex {
+        (BlockSpec::Number(0xe8), 0x0) => json!(["0xe8", "0x0"]);
+        Some(Header {
+            hash: digest!("0x03ae0cb6d3a1c2c4fd6a2d8a0b4f3bc1e4bf6d6e1d3a3ae2f0bd5b3b4bd2d4c1"),
+            parent_hash: digest!("0x5b4b5a8d71ac6e7ddb8b1d38e9c35e0f67ec8fc5aad2cf4b7d1bb5a0b2cb6fa1"),
+            sha3_uncles: digest!("0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"),
+            miner: address!("0xB42b6c4A95406C78FF892D270AD20b22642e102D"),
+            state_root: digest!("0x8bb7e4ba4bf1a2c9aa1a0e7f09b0d7b1e8b29e13bb8c4c2d37a7fb14e2a1c3a4"),
+            transactions_root: digest!("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+            receipts_root: digest!("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+            logs_bloom: Bloom([0; 256]),
+            difficulty: uint!("0x4417decc"),
+            number: 0xe7,
+            gas_limit: 0x1388,
+            gas_used: 0x0,
+            timestamp: 0x55ba43eb,
+            extra_data: hex!("476574682f76312e302e302f6c696e75782f676f312e342e32").to_vec(),
+            mix_hash: digest!("0x1d6d4d5b16cbc2c5c4cc11e1b8dd8e6e1b2ac0e35b5ce7ce3ccbca1c1b7c9c5d"),
+            nonce: BlockNonce(hex!("5fdc1a2b3c4d5e6f")),
+            base_fee_per_gas: uint!("0x0"),
+            withdrawals_root: Digest::default(),
+            blob_gas_used: 0x0,
+            excess_blob_gas: 0x0,
+            parent_beacon_block_root: Digest::default(),
+            requests_hash: Digest::default(),
+        }) => json!({
+            "baseFeePerGas": "0x0",
+            "blobGasUsed": "0x0",
+            "difficulty": "0x4417decc",
+            "excessBlobGas": "0x0",
+            "extraData": "0x476574682f76312e302e302f6c696e75782f676f312e342e32",
+            "gasLimit": "0x1388",
+            "gasUsed": "0x0",
+            "hash": "0x03ae0cb6d3a1c2c4fd6a2d8a0b4f3bc1e4bf6d6e1d3a3ae2f0bd5b3b4bd2d4c1",
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "miner": "0xB42b6c4A95406C78FF892D270AD20b22642e102D",
+            "mixHash": "0x1d6d4d5b16cbc2c5c4cc11e1b8dd8e6e1b2ac0e35b5ce7ce3ccbca1c1b7c9c5d",
+            "nonce": "0x5fdc1a2b3c4d5e6f",
+            "number": "0xe7",
+            "parentBeaconBlockRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "parentHash": "0x5b4b5a8d71ac6e7ddb8b1d38e9c35e0f67ec8fc5aad2cf4b7d1bb5a0b2cb6fa1",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "requestsHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "stateRoot": "0x8bb7e4ba4bf1a2c9aa1a0e7f09b0d7b1e8b29e13bb8c4c2d37a7fb14e2a1c3a4",
+            "timestamp": "0x55ba43eb",
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "withdrawalsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        });
+    });
+}
+
+#[test]
+fn eth_get_uncle_count_by_block_hash() {
+    assert_method_serialization!(eth::GetUncleCountByBlockHash {
+        (digest!("0xb903239f8543d04b5dc1ba6579132b143087c68db1b2168786408fcbce568238"),)
+            => json!(["0xb903239f8543d04b5dc1ba6579132b143087c68db1b2168786408fcbce568238"]);
+        Some(0x1) => json!("0x1");
+    });
+}
+
+#[test]
+fn eth_get_uncle_count_by_block_number() {
+    assert_method_serialization!(eth::GetUncleCountByBlockNumber {
+        (BlockSpec::Number(0xe8),) => json!(["0xe8"]);
+        Some(0x0) => json!("0x0");
+    });
+}
+
+#[test]
+fn eth_hashrate() {
+    assert_method_serialization!(eth::Hashrate {
+        Empty => json!([]);
+        uint!("0x38a") => json!("0x38a");
+    });
+}
+
 #[test]
 fn eth_max_priority_fee_per_gas() {
     assert_method_serialization!(eth::MaxPriorityFeePerGas {
@@ -1527,6 +1716,14 @@ fn eth_max_priority_fee_per_gas() {
     });
 }
 
+#[test]
+fn eth_mining() {
+    assert_method_serialization!(eth::Mining {
+        Empty => json!([]);
+        true => json!(true);
+    });
+}
+
 #[test]
 fn eth_new_block_filter() {
     assert_method_serialization!(eth::NewBlockFilter {
@@ -1563,6 +1760,14 @@ fn eth_new_pending_transaction_filter() {
     });
 }
 
+#[test]
+fn eth_protocol_version() {
+    assert_method_serialization!(eth::ProtocolVersion {
+        Empty => json!([]);
+        0x44 => json!("0x44");
+    });
+}
+
 #[test]
 fn eth_send_raw_transaction() {
     assert_method_serialization!(eth::SendRawTransaction {
@@ -1795,6 +2000,41 @@ fn eth_simulate_v1() {
     });
 }
 
+#[test]
+fn eth_subscribe() {
+    assert_method_serialization!(eth::Subscribe {
+        SubscriptionKind::NewHeads => json!(["newHeads"]);
+        SubscriptionId::from_raw("0x9cef478923ff08bf67fde6c64013158d".to_owned())
+            => json!("0x9cef478923ff08bf67fde6c64013158d");
+    });
+
+    assert_method_serialization!(eth::Subscribe {
+        SubscriptionKind::Logs(LogFilter {
+            address: LogFilterValue::Exact(address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41")),
+            topics: [
+                LogFilterValue::Exact(digest!("0xd78a0cb8bb633d06981248b816e7bd33c2a35a6089241d099fa519e361cab902")),
+            ].into_iter().collect(),
+            ..Default::default()
+        }) => json!([
+            "logs",
+            {
+                "fromBlock": "latest",
+                "toBlock": "latest",
+                "address": "0x9008D19f58AAbD9eD0D60971565AA8510560ab41",
+                "topics": ["0xd78a0cb8bb633d06981248b816e7bd33c2a35a6089241d099fa519e361cab902"],
+            },
+        ]);
+        SubscriptionId::from_raw("0x4a8a4c0517381924f9838102c5a4dcb7".to_owned())
+            => json!("0x4a8a4c0517381924f9838102c5a4dcb7");
+    });
+
+    assert_method_serialization!(eth::Subscribe {
+        SubscriptionKind::NewPendingTransactions => json!(["newPendingTransactions"]);
+        SubscriptionId::from_raw("0xc3b33aa549fb9a60e95d21862596617c".to_owned())
+            => json!("0xc3b33aa549fb9a60e95d21862596617c");
+    });
+}
+
 #[test]
 fn eth_syncing() {
     assert_method_serialization!(eth::Syncing {
@@ -1823,3 +2063,1421 @@ fn eth_uninstall_filter() {
         true => json!(true);
     })
 }
+
+#[test]
+fn eth_unsubscribe() {
+    assert_method_serialization!(eth::Unsubscribe {
+        (SubscriptionId::from_raw("0x9cef478923ff08bf67fde6c64013158d".to_owned()),)
+            => json!(["0x9cef478923ff08bf67fde6c64013158d"]);
+        true => json!(true);
+    })
+}
+
+#[test]
+fn debug_get_raw_block() {
+    assert_method_serialization!(debug::GetRawBlock {
+        (BlockId::Number(0x0),) => json!(["0x0"]);
+        hex!("f90200c0c0").to_vec() => json!("0xf90200c0c0");
+    });
+}
+
+#[test]
+fn debug_get_raw_header() {
+    assert_method_serialization!(debug::GetRawHeader {
+        (BlockId::Tag(BlockTag::Latest),) => json!(["latest"]);
+        hex!("f9021ca01f68ac259155e2f3").to_vec() => json!("0xf9021ca01f68ac259155e2f3");
+    });
+}
+
+#[test]
+fn debug_get_raw_receipts() {
+    assert_method_serialization!(debug::GetRawReceipts {
+        (BlockId::Hash(digest!("0xd5f1812548be429cbdc6376b29611fc49e06f1359758c4ceaaa3b393e2239f9c")),)
+            => json!(["0xd5f1812548be429cbdc6376b29611fc49e06f1359758c4ceaaa3b393e2239f9c"]);
+        vec![
+            hex!("f901090182520800c0").to_vec(),
+            hex!("02f9010901825208c0").to_vec(),
+        ] => json!(["0xf901090182520800c0", "0x02f9010901825208c0"]);
+    });
+}
+
+#[test]
+fn debug_get_raw_transaction() {
+    assert_method_serialization!(debug::GetRawTransaction {
+        (digest!("0xa0807e117a8dd124ab949f460f08c36c72b710188f01609595223b325e58e0fc"),)
+            => json!(["0xa0807e117a8dd124ab949f460f08c36c72b710188f01609595223b325e58e0fc"]);
+        hex!("02f86a0180843b9aca00843b9aca0082520894000000000000000000000000000000000000000080c0").to_vec()
+            => json!("0x02f86a0180843b9aca00843b9aca0082520894000000000000000000000000000000000000000080c0");
+    });
+}
+
+#[test]
+fn debug_trace_block_by_hash() {
+    assert_method_serialization!(debug::TraceBlockByHash {
+        (
+            digest!("0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c"),
+            TraceConfig {
+                tracer: Tracer::Custom {
+                    code: "{data: [], fault: function(log) {}, step: function(log) {}, result: function() { return this.data; }}".to_owned(),
+                    config: None,
+                },
+                timeout: Some("10s".to_owned()),
+                ..Default::default()
+            },
+        ) => json!([
+            "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c",
+            {
+                "tracer": "{data: [], fault: function(log) {}, step: function(log) {}, result: function() { return this.data; }}",
+                "timeout": "10s",
+            },
+        ]);
+        vec![
+            TransactionTrace {
+                tx_hash: digest!("0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"),
+                result: Some(Trace::Custom(jsonrpc::Value(json!([1, 2, 3])))),
+                error: None,
+            },
+            TransactionTrace {
+                tx_hash: digest!("0x1a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"),
+                result: None,
+                error: Some("execution timeout".to_owned()),
+            },
+        ] => json!([
+            {
+                "txHash": "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+                "result": [1, 2, 3],
+            },
+            {
+                "txHash": "0x1a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+                "error": "execution timeout",
+            },
+        ]);
+    });
+}
+
+#[test]
+fn debug_trace_block_by_number() {
+    assert_method_serialization!(debug::TraceBlockByNumber {
+        (
+            BlockSpec::Number(0xccde12),
+            TraceConfig {
+                tracer: Tracer::FourByte,
+                ..Default::default()
+            },
+        ) => json!([
+            "0xccde12",
+            {
+                "tracer": "4byteTracer",
+            },
+        ]);
+        vec![
+            TransactionTrace {
+                tx_hash: digest!("0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"),
+                result: Some(Trace::FourByte(HashMap::from([
+                    ("0x27dc297e-128".to_owned(), 1),
+                    ("0x38cc4831-0".to_owned(), 2),
+                ]))),
+                error: None,
+            },
+        ] => json!([
+            {
+                "txHash": "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+                "result": {
+                    "0x27dc297e-128": 1,
+                    "0x38cc4831-0": 2,
+                },
+            },
+        ]);
+    });
+}
+
+#[test]
+fn debug_trace_call() {
+    assert_method_serialization!(debug::TraceCall {
+        (
+            Transaction {
+                from: Some(address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B")),
+                to: Some(address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13")),
+                value: Some(uint!("0x1")),
+                ..Default::default()
+            },
+            BlockId::Tag(BlockTag::Latest),
+            TraceConfig {
+                tracer: Tracer::Prestate(PrestateTracerConfig {
+                    diff_mode: Some(true),
+                    ..Default::default()
+                }),
+                state_overrides: Some(HashMap::from([(
+                    address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+                    AccountOverrides {
+                        balance: Some(uint!("0xde0b6b3a7640000")),
+                        ..Default::default()
+                    },
+                )])),
+                ..Default::default()
+            },
+        ) => json!([
+            {
+                "from": "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B",
+                "to": "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+                "value": "0x1",
+            },
+            "latest",
+            {
+                "tracer": "prestateTracer",
+                "tracerConfig": {
+                    "diffMode": true,
+                },
+                "stateOverrides": {
+                    "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B": {
+                        "balance": "0xde0b6b3a7640000",
+                    },
+                },
+            },
+        ]);
+        Trace::PrestateDiff(PrestateDiffTrace {
+            pre: HashMap::from([
+                (
+                    address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+                    PrestateAccount {
+                        balance: Some(uint!("0xde0b6b3a7640000")),
+                        nonce: Some(1),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"),
+                    PrestateAccount {
+                        balance: Some(uint!("0x0")),
+                        code: Some(hex!("6080").to_vec()),
+                        storage: Some(HashMap::from([(
+                            digest!("0x0000000000000000000000000000000000000000000000000000000000000000"),
+                            digest!("0x0000000000000000000000000000000000000000000000000000000000000001"),
+                        )])),
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            post: HashMap::from([(
+                address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"),
+                PrestateAccount {
+                    balance: Some(uint!("0x1")),
+                    ..Default::default()
+                },
+            )]),
+        }) => json!({
+            "pre": {
+                "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B": {
+                    "balance": "0xde0b6b3a7640000",
+                    "nonce": 1,
+                },
+                "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13": {
+                    "balance": "0x0",
+                    "code": "0x6080",
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000":
+                            "0x0000000000000000000000000000000000000000000000000000000000000001",
+                    },
+                },
+            },
+            "post": {
+                "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13": {
+                    "balance": "0x1",
+                },
+            },
+        });
+    });
+}
+
+#[test]
+fn debug_trace_transaction() {
+    assert_method_serialization!(debug::TraceTransaction {
+        (
+            digest!("0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"),
+            TraceConfig {
+                tracer: Tracer::Call(CallTracerConfig {
+                    with_log: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ) => json!([
+            "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+            {
+                "tracer": "callTracer",
+                "tracerConfig": {
+                    "withLog": true,
+                },
+            },
+        ]);
+        Trace::Call(CallFrame {
+            kind: CallKind::Call,
+            from: address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+            to: Some(address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41")),
+            value: Some(uint!("0x0")),
+            gas: 0x2dc6c0,
+            gas_used: 0x1f2a4,
+            input: hex!("13d79a0b").to_vec(),
+            output: None,
+            error: Some("execution reverted".to_owned()),
+            revert_reason: Some("GPv2: order expired".to_owned()),
+            logs: vec![],
+            calls: vec![CallFrame {
+                kind: CallKind::DelegateCall,
+                from: address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41"),
+                to: Some(address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13")),
+                value: None,
+                gas: 0x2d0000,
+                gas_used: 0x1000,
+                input: hex!("a9059cbb").to_vec(),
+                output: Some(hex!("01").to_vec()),
+                error: None,
+                revert_reason: None,
+                logs: vec![CallLog {
+                    address: address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"),
+                    topics: [digest!(
+                        "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+                    )]
+                    .into_iter()
+                    .collect(),
+                    data: hex!("2a").to_vec(),
+                    position: Some(0),
+                }],
+                calls: vec![],
+            }],
+        }) => json!({
+            "type": "CALL",
+            "from": "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B",
+            "to": "0x9008D19f58AAbD9eD0D60971565AA8510560ab41",
+            "value": "0x0",
+            "gas": "0x2dc6c0",
+            "gasUsed": "0x1f2a4",
+            "input": "0x13d79a0b",
+            "error": "execution reverted",
+            "revertReason": "GPv2: order expired",
+            "calls": [
+                {
+                    "type": "DELEGATECALL",
+                    "from": "0x9008D19f58AAbD9eD0D60971565AA8510560ab41",
+                    "to": "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+                    "gas": "0x2d0000",
+                    "gasUsed": "0x1000",
+                    "input": "0xa9059cbb",
+                    "output": "0x01",
+                    "logs": [
+                        {
+                            "address": "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+                            "topics": [
+                                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            ],
+                            "data": "0x2a",
+                            "position": "0x0",
+                        },
+                    ],
+                },
+            ],
+        });
+    });
+}
+
+#[test]
+fn debug_trace_transaction_struct_logs() {
+    assert_method_serialization!(debug::TraceTransaction {
+        (
+            digest!("0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"),
+            TraceConfig {
+                tracer: Tracer::StructLogger(StructLoggerConfig {
+                    disable_storage: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ) => json!([
+            "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+            {
+                "disableStorage": true,
+            },
+        ]);
+        Trace::StructLogs(StructLogTrace {
+            gas: 21003,
+            failed: false,
+            return_value: "0x".to_owned(),
+            struct_logs: vec![StructLog {
+                pc: 0,
+                op: "PUSH1".to_owned(),
+                gas: 78979,
+                gas_cost: 3,
+                depth: 1,
+                stack: Some(vec![]),
+                memory: None,
+                storage: None,
+                refund: None,
+                error: None,
+            }],
+        }) => json!({
+            "gas": 21003,
+            "failed": false,
+            "returnValue": "0x",
+            "structLogs": [
+                {
+                    "pc": 0,
+                    "op": "PUSH1",
+                    "gas": 78979,
+                    "gasCost": 3,
+                    "depth": 1,
+                    "stack": [],
+                },
+            ],
+        });
+    });
+}
+
+fn execution_payload() -> (ExecutionPayload, serde_json::Value) {
+    (
+        ExecutionPayload {
+            parent_hash: digest!(
+                "0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a"
+            ),
+            fee_recipient: address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+            state_root: digest!(
+                "0xca3149fa9e37db08d1cd49c9061db1002ef1cd58db2210f2115c8c989b2bdf45"
+            ),
+            receipts_root: digest!(
+                "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+            ),
+            logs_bloom: Bloom::default(),
+            prev_randao: Digest::default(),
+            block_number: 1,
+            gas_limit: 0x1c9c380,
+            gas_used: 0,
+            timestamp: 0x5,
+            extra_data: vec![],
+            base_fee_per_gas: uint!("0x7"),
+            block_hash: digest!(
+                "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c"
+            ),
+            transactions: vec![hex!("02f0").to_vec()],
+            withdrawals: Some(vec![Withdrawal {
+                address: address!("0x00000000000000000000000000000000000010f0"),
+                amount: 0x1,
+                index: 0xf0,
+                validator_index: 0xf0,
+            }]),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+        },
+        json!({
+            "parentHash": "0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a",
+            "feeRecipient": "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B",
+            "stateRoot": "0xca3149fa9e37db08d1cd49c9061db1002ef1cd58db2210f2115c8c989b2bdf45",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "prevRandao": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "blockNumber": "0x1",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "timestamp": "0x5",
+            "extraData": "0x",
+            "baseFeePerGas": "0x7",
+            "blockHash": "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c",
+            "transactions": ["0x02f0"],
+            "withdrawals": [
+                {
+                    "address": "0x00000000000000000000000000000000000010f0",
+                    "amount": "0x1",
+                    "index": "0xf0",
+                    "validatorIndex": "0xf0",
+                },
+            ],
+            "blobGasUsed": "0x0",
+            "excessBlobGas": "0x0",
+        }),
+    )
+}
+
+#[test]
+fn engine_exchange_capabilities() {
+    assert_method_serialization!(engine::ExchangeCapabilities {
+        (vec!["engine_newPayloadV4".to_owned()],) => json!([["engine_newPayloadV4"]]);
+        vec![
+            "engine_newPayloadV4".to_owned(),
+            "engine_getPayloadV4".to_owned(),
+        ] => json!(["engine_newPayloadV4", "engine_getPayloadV4"]);
+    });
+}
+
+#[test]
+fn engine_forkchoice_updated_v3() {
+    assert_method_serialization!(engine::ForkchoiceUpdatedV3 {
+        (
+            ForkchoiceState {
+                head_block_hash: digest!(
+                    "0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a"
+                ),
+                safe_block_hash: digest!(
+                    "0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a"
+                ),
+                finalized_block_hash: Digest::default(),
+            },
+            Some(PayloadAttributes {
+                timestamp: 0x5,
+                prev_randao: Digest::default(),
+                suggested_fee_recipient: address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+                withdrawals: Some(vec![]),
+                parent_beacon_block_root: Some(Digest::default()),
+            }),
+        ) => json!([
+            {
+                "headBlockHash": "0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a",
+                "safeBlockHash": "0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a",
+                "finalizedBlockHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            },
+            {
+                "timestamp": "0x5",
+                "prevRandao": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "suggestedFeeRecipient": "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B",
+                "withdrawals": [],
+                "parentBeaconBlockRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            },
+        ]);
+        ForkchoiceUpdatedResult {
+            payload_status: PayloadStatus {
+                status: PayloadStatusKind::Valid,
+                latest_valid_hash: Some(digest!(
+                    "0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a"
+                )),
+                validation_error: None,
+            },
+            payload_id: Some(PayloadId(hex!("a247243752eb10b4"))),
+        } => json!({
+            "payloadStatus": {
+                "status": "VALID",
+                "latestValidHash": "0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a",
+                "validationError": null,
+            },
+            "payloadId": "0xa247243752eb10b4",
+        });
+    });
+}
+
+#[test]
+fn engine_get_blobs_v1() {
+    assert_method_serialization!(engine::GetBlobsV1 {
+        (vec![
+            digest!("0x01d18459b334ffe8e2226eef1db874fda6db2bdd9357268b39220af2d59464fb"),
+            digest!("0x01a2e1b3c4d5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1"),
+        ],) => json!([[
+            "0x01d18459b334ffe8e2226eef1db874fda6db2bdd9357268b39220af2d59464fb",
+            "0x01a2e1b3c4d5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1",
+        ]]);
+        vec![
+            Some(BlobAndProof {
+                blob: hex!("0102").to_vec(),
+                proof: hex!("0304").to_vec(),
+            }),
+            None,
+        ] => json!([
+            {
+                "blob": "0x0102",
+                "proof": "0x0304",
+            },
+            null,
+        ]);
+    });
+}
+
+#[test]
+fn engine_get_payload_v1() {
+    let (mut payload, mut json) = execution_payload();
+    payload.withdrawals = None;
+    payload.blob_gas_used = None;
+    payload.excess_blob_gas = None;
+    for field in ["withdrawals", "blobGasUsed", "excessBlobGas"] {
+        json.as_object_mut().unwrap().remove(field);
+    }
+
+    assert_method_serialization!(engine::GetPayloadV1 {
+        (PayloadId(hex!("a247243752eb10b4")),) => json!(["0xa247243752eb10b4"]);
+        payload => json;
+    });
+}
+
+#[test]
+fn engine_get_payload_v4() {
+    let (payload, json) = execution_payload();
+    assert_method_serialization!(engine::GetPayloadV4 {
+        (PayloadId(hex!("a247243752eb10b4")),) => json!(["0xa247243752eb10b4"]);
+        ExecutionPayloadEnvelope {
+            execution_payload: payload,
+            block_value: uint!("0x1b681c8f4b08c"),
+            blobs_bundle: Some(BlobsBundle::default()),
+            should_override_builder: Some(false),
+            execution_requests: Some(vec![hex!("0001").to_vec()]),
+        } => json!({
+            "executionPayload": json,
+            "blockValue": "0x1b681c8f4b08c",
+            "blobsBundle": {
+                "commitments": [],
+                "proofs": [],
+                "blobs": [],
+            },
+            "shouldOverrideBuilder": false,
+            "executionRequests": ["0x0001"],
+        });
+    });
+}
+
+#[test]
+fn engine_new_payload_v4() {
+    let (payload, json) = execution_payload();
+    assert_method_serialization!(engine::NewPayloadV4 {
+        (
+            payload,
+            vec![],
+            digest!("0x169630f535b4a41330164c6e5c92b1224c0c407f582d407d0ac3d206cd32fd52"),
+            vec![hex!("0001").to_vec()],
+        ) => json!([
+            json,
+            [],
+            "0x169630f535b4a41330164c6e5c92b1224c0c407f582d407d0ac3d206cd32fd52",
+            ["0x0001"],
+        ]);
+        PayloadStatus {
+            status: PayloadStatusKind::Invalid,
+            latest_valid_hash: None,
+            validation_error: Some("invalid block hash".to_owned()),
+        } => json!({
+            "status": "INVALID",
+            "latestValidHash": null,
+            "validationError": "invalid block hash",
+        });
+    });
+}
+
+#[test]
+fn trace_block() {
+    assert_method_serialization!(trace::Block {
+        (BlockSpec::Number(0xccde12),) => json!(["0xccde12"]);
+        Some(vec![
+            LocalizedTrace {
+                trace: ActionTrace {
+                    action: TraceAction::Call(CallAction {
+                        from: address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+                        to: address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41"),
+                        value: uint!("0x0"),
+                        gas: 0x2dc6c0,
+                        input: hex!("13d79a0b").to_vec(),
+                        call_type: CallActionKind::Call,
+                    }),
+                    result: Some(TraceOutput::Call(CallOutput {
+                        gas_used: 0x1f2a4,
+                        output: vec![],
+                    })),
+                    error: None,
+                    subtraces: 1,
+                    trace_address: vec![],
+                },
+                block_hash: digest!(
+                    "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c"
+                ),
+                block_number: 13426194,
+                transaction_hash: Some(digest!(
+                    "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"
+                )),
+                transaction_position: Some(0),
+            },
+            LocalizedTrace {
+                trace: ActionTrace {
+                    action: TraceAction::Create(CreateAction {
+                        from: address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41"),
+                        value: uint!("0x0"),
+                        gas: 0x10000,
+                        init: hex!("6080").to_vec(),
+                    }),
+                    result: Some(TraceOutput::Create(CreateOutput {
+                        address: address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"),
+                        code: hex!("60").to_vec(),
+                        gas_used: 0x100,
+                    })),
+                    error: None,
+                    subtraces: 0,
+                    trace_address: vec![0],
+                },
+                block_hash: digest!(
+                    "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c"
+                ),
+                block_number: 13426194,
+                transaction_hash: Some(digest!(
+                    "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"
+                )),
+                transaction_position: Some(0),
+            },
+            LocalizedTrace {
+                trace: ActionTrace {
+                    action: TraceAction::Reward(RewardAction {
+                        author: address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+                        value: uint!("0x1bc16d674ec80000"),
+                        reward_type: RewardKind::Block,
+                    }),
+                    result: None,
+                    error: None,
+                    subtraces: 0,
+                    trace_address: vec![],
+                },
+                block_hash: digest!(
+                    "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c"
+                ),
+                block_number: 13426194,
+                transaction_hash: None,
+                transaction_position: None,
+            },
+        ]) => json!([
+            {
+                "action": {
+                    "from": "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B",
+                    "to": "0x9008D19f58AAbD9eD0D60971565AA8510560ab41",
+                    "value": "0x0",
+                    "gas": "0x2dc6c0",
+                    "input": "0x13d79a0b",
+                    "callType": "call",
+                },
+                "blockHash": "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c",
+                "blockNumber": 13426194,
+                "result": {
+                    "gasUsed": "0x1f2a4",
+                    "output": "0x",
+                },
+                "subtraces": 1,
+                "traceAddress": [],
+                "transactionHash": "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+                "transactionPosition": 0,
+                "type": "call",
+            },
+            {
+                "action": {
+                    "from": "0x9008D19f58AAbD9eD0D60971565AA8510560ab41",
+                    "value": "0x0",
+                    "gas": "0x10000",
+                    "init": "0x6080",
+                },
+                "blockHash": "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c",
+                "blockNumber": 13426194,
+                "result": {
+                    "address": "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+                    "code": "0x60",
+                    "gasUsed": "0x100",
+                },
+                "subtraces": 0,
+                "traceAddress": [0],
+                "transactionHash": "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+                "transactionPosition": 0,
+                "type": "create",
+            },
+            {
+                "action": {
+                    "author": "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B",
+                    "value": "0x1bc16d674ec80000",
+                    "rewardType": "block",
+                },
+                "blockHash": "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c",
+                "blockNumber": 13426194,
+                "result": null,
+                "subtraces": 0,
+                "traceAddress": [],
+                "transactionHash": null,
+                "transactionPosition": null,
+                "type": "reward",
+            },
+        ]);
+    });
+}
+
+#[test]
+fn trace_filter() {
+    assert_method_serialization!(trace::Filter {
+        (TraceFilter {
+            from_block: Some(BlockSpec::Number(0xccde12)),
+            to_block: Some(BlockSpec::Tag(BlockTag::Latest)),
+            to_address: Some(vec![address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41")]),
+            count: Some(10),
+            ..Default::default()
+        },) => json!([{
+            "fromBlock": "0xccde12",
+            "toBlock": "latest",
+            "toAddress": ["0x9008D19f58AAbD9eD0D60971565AA8510560ab41"],
+            "count": 10,
+        }]);
+        vec![LocalizedTrace {
+            trace: ActionTrace {
+                action: TraceAction::SelfDestruct(SelfDestructAction {
+                    address: address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"),
+                    refund_address: address!("0x9008D19f58AAbD9eD0D60971565AA8510560ab41"),
+                    balance: uint!("0x1"),
+                }),
+                result: None,
+                error: None,
+                subtraces: 0,
+                trace_address: vec![0, 1],
+            },
+            block_hash: digest!(
+                "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c"
+            ),
+            block_number: 13426194,
+            transaction_hash: Some(digest!(
+                "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"
+            )),
+            transaction_position: Some(3),
+        }] => json!([{
+            "action": {
+                "address": "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+                "refundAddress": "0x9008D19f58AAbD9eD0D60971565AA8510560ab41",
+                "balance": "0x1",
+            },
+            "blockHash": "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c",
+            "blockNumber": 13426194,
+            "result": null,
+            "subtraces": 0,
+            "traceAddress": [0, 1],
+            "transactionHash": "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+            "transactionPosition": 3,
+            "type": "suicide",
+        }]);
+    });
+}
+
+#[test]
+fn trace_replay_transaction() {
+    assert_method_serialization!(trace::ReplayTransaction {
+        (
+            digest!("0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"),
+            vec![TraceType::VmTrace, TraceType::StateDiff],
+        ) => json!([
+            "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+            ["vmTrace", "stateDiff"],
+        ]);
+        TraceResults {
+            output: hex!("01").to_vec(),
+            state_diff: Some(HashMap::from([(
+                address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"),
+                AccountDiff {
+                    balance: Delta::Changed(Change {
+                        from: uint!("0x1"),
+                        to: uint!("0x2"),
+                    }),
+                    nonce: Delta::Unchanged,
+                    code: Delta::Added(hex!("6080").to_vec()),
+                    storage: HashMap::from([(
+                        digest!("0x0000000000000000000000000000000000000000000000000000000000000000"),
+                        Delta::Removed(digest!(
+                            "0x0000000000000000000000000000000000000000000000000000000000000001"
+                        )),
+                    )]),
+                },
+            )])),
+            trace: vec![],
+            vm_trace: Some(VmTrace {
+                code: hex!("600160005500").to_vec(),
+                ops: vec![
+                    VmInstruction {
+                        pc: 0,
+                        cost: 3,
+                        ex: Some(VmExecutedOperation {
+                            used: 78997,
+                            push: vec![uint!("0x1")],
+                            mem: None,
+                            store: None,
+                        }),
+                        sub: None,
+                    },
+                    VmInstruction {
+                        pc: 4,
+                        cost: 20000,
+                        ex: Some(VmExecutedOperation {
+                            used: 58994,
+                            push: vec![],
+                            mem: Some(VmMemoryDiff {
+                                off: 0,
+                                data: hex!("2a").to_vec(),
+                            }),
+                            store: Some(VmStorageDiff {
+                                key: uint!("0x0"),
+                                val: uint!("0x1"),
+                            }),
+                        }),
+                        sub: None,
+                    },
+                ],
+            }),
+            transaction_hash: None,
+        } => json!({
+            "output": "0x01",
+            "stateDiff": {
+                "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13": {
+                    "balance": {
+                        "*": {
+                            "from": "0x1",
+                            "to": "0x2",
+                        },
+                    },
+                    "nonce": "=",
+                    "code": {
+                        "+": "0x6080",
+                    },
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000": {
+                            "-": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                        },
+                    },
+                },
+            },
+            "trace": [],
+            "vmTrace": {
+                "code": "0x600160005500",
+                "ops": [
+                    {
+                        "pc": 0,
+                        "cost": 3,
+                        "ex": {
+                            "used": 78997,
+                            "push": ["0x1"],
+                            "mem": null,
+                            "store": null,
+                        },
+                        "sub": null,
+                    },
+                    {
+                        "pc": 4,
+                        "cost": 20000,
+                        "ex": {
+                            "used": 58994,
+                            "push": [],
+                            "mem": {
+                                "off": 0,
+                                "data": "0x2a",
+                            },
+                            "store": {
+                                "key": "0x0",
+                                "val": "0x1",
+                            },
+                        },
+                        "sub": null,
+                    },
+                ],
+            },
+        });
+    });
+}
+
+#[test]
+fn txpool_content() {
+    assert_method_serialization!(txpool::Content {
+        Empty => json!([]);
+        TxpoolContent {
+            pending: HashMap::from([(
+                address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+                BTreeMap::from([(
+                    7,
+                    PendingTransaction {
+                        transaction: SignedTransaction::Eip1559(SignedEip1559Transaction {
+                            block_hash: Digest::default(),
+                            block_number: 0,
+                            block_timestamp: None,
+                            from: address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+                            gas: 0x5208,
+                            max_fee_per_gas: uint!("0x77359400"),
+                            max_priority_fee_per_gas: uint!("0x3b9aca00"),
+                            hash: digest!(
+                                "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80"
+                            ),
+                            input: vec![],
+                            nonce: 7,
+                            to: Some(address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13")),
+                            transaction_index: 0,
+                            value: uint!("0x1"),
+                            access_list: vec![],
+                            chain_id: 1,
+                            r: uint!("0x1"),
+                            s: uint!("0x2"),
+                            y_parity: YParity::Odd,
+                        }),
+                    },
+                )]),
+            )]),
+            queued: HashMap::new(),
+        } => json!({
+            "pending": {
+                "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B": {
+                    "7": {
+                        "type": "0x2",
+                        "blockHash": null,
+                        "blockNumber": null,
+                        "transactionIndex": null,
+                        "from": "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B",
+                        "gas": "0x5208",
+                        "maxFeePerGas": "0x77359400",
+                        "maxPriorityFeePerGas": "0x3b9aca00",
+                        "hash": "0x0a3e9cbd0b1a3d5b8f4c56e0a1a1c6b2c32b8d3c7e1d5a0e1c2a3b4c5d6e7f80",
+                        "input": "0x",
+                        "nonce": "0x7",
+                        "to": "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+                        "value": "0x1",
+                        "accessList": [],
+                        "chainId": "0x1",
+                        "r": "0x1",
+                        "s": "0x2",
+                        "yParity": "0x1",
+                    },
+                },
+            },
+            "queued": {},
+        });
+    });
+}
+
+#[test]
+fn txpool_content_from() {
+    assert_method_serialization!(txpool::ContentFrom {
+        (address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),)
+            => json!(["0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"]);
+        TxpoolContentFrom {
+            pending: BTreeMap::new(),
+            queued: BTreeMap::from([(
+                9,
+                PendingTransaction {
+                    transaction: SignedTransaction::Legacy(SignedLegacyTransaction {
+                        block_hash: Digest::default(),
+                        block_number: 0,
+                        block_timestamp: None,
+                        from: address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+                        gas: 0x5208,
+                        gas_price: uint!("0x77359400"),
+                        hash: digest!(
+                            "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"
+                        ),
+                        input: vec![],
+                        nonce: 9,
+                        to: Some(address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13")),
+                        transaction_index: 0,
+                        value: uint!("0x1"),
+                        chain_id: Some(1),
+                        v: 0x25,
+                        r: uint!("0x3"),
+                        s: uint!("0x4"),
+                    }),
+                },
+            )]),
+        } => json!({
+            "pending": {},
+            "queued": {
+                "9": {
+                    "type": "0x0",
+                    "blockHash": null,
+                    "blockNumber": null,
+                    "transactionIndex": null,
+                    "hash": "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+                    "nonce": "0x9",
+                    "to": "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+                    "from": "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B",
+                    "gas": "0x5208",
+                    "value": "0x1",
+                    "input": "0x",
+                    "gasPrice": "0x77359400",
+                    "chainId": "0x1",
+                    "v": "0x25",
+                    "r": "0x3",
+                    "s": "0x4",
+                },
+            },
+        });
+    });
+}
+
+#[test]
+fn txpool_inspect() {
+    assert_method_serialization!(txpool::Inspect {
+        Empty => json!([]);
+        TxpoolInspect {
+            pending: HashMap::new(),
+            queued: HashMap::from([(
+                address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"),
+                BTreeMap::from([(
+                    9,
+                    "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13: 1 wei + 21000 gas × 2000000000 wei"
+                        .to_owned(),
+                )]),
+            )]),
+        } => json!({
+            "pending": {},
+            "queued": {
+                "0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B": {
+                    "9": "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13: 1 wei + 21000 gas × 2000000000 wei",
+                },
+            },
+        });
+    });
+}
+
+#[test]
+fn txpool_status() {
+    assert_method_serialization!(txpool::Status {
+        Empty => json!([]);
+        TxpoolStatus {
+            pending: 0xa,
+            queued: 0x7,
+        } => json!({
+            "pending": "0xa",
+            "queued": "0x7",
+        });
+    });
+}
+
+#[test]
+fn net_listening() {
+    assert_method_serialization!(net::Listening {
+        Empty => json!([]);
+        true => json!(true);
+    });
+}
+
+#[test]
+fn net_peer_count() {
+    assert_method_serialization!(net::PeerCount {
+        Empty => json!([]);
+        0x19 => json!("0x19");
+    });
+}
+
+#[test]
+fn web3_client_version() {
+    assert_method_serialization!(web3::ClientVersion {
+        Empty => json!([]);
+        ClientVersion {
+            name: "Geth".to_owned(),
+            identity: None,
+            version: Some("v1.14.11-stable-f3c696fa".to_owned()),
+            os: Some("linux-amd64".to_owned()),
+            language: Some("go1.23.2".to_owned()),
+        } => json!("Geth/v1.14.11-stable-f3c696fa/linux-amd64/go1.23.2");
+    });
+}
+
+#[test]
+fn client_versions() {
+    for (version, kind, name, identity, number) in [
+        (
+            "Geth/my-node/v1.14.11-stable-f3c696fa/linux-amd64/go1.23.2",
+            ClientKind::Geth,
+            "Geth",
+            Some("my-node"),
+            Some("v1.14.11-stable-f3c696fa"),
+        ),
+        (
+            "reth/v1.1.0-1ba631b/x86_64-unknown-linux-gnu",
+            ClientKind::Reth,
+            "reth",
+            None,
+            Some("v1.1.0-1ba631b"),
+        ),
+        (
+            "erigon/2.60.8/linux-amd64/go1.22.7",
+            ClientKind::Erigon,
+            "erigon",
+            None,
+            Some("2.60.8"),
+        ),
+        (
+            "Nethermind/v1.29.0+1c15d9ec/linux-x64/dotnet8.0.8",
+            ClientKind::Nethermind,
+            "Nethermind",
+            None,
+            Some("v1.29.0+1c15d9ec"),
+        ),
+        (
+            "HardhatNetwork/2.22.15/@ethereumjs/vm/7.0.0",
+            ClientKind::Hardhat,
+            "HardhatNetwork",
+            None,
+            Some("2.22.15"),
+        ),
+        (
+            "anvil/v0.2.0",
+            ClientKind::Anvil,
+            "anvil",
+            None,
+            Some("v0.2.0"),
+        ),
+        ("custom", ClientKind::Other, "custom", None, None),
+    ] {
+        let parsed = version.parse::<ClientVersion>().unwrap();
+        assert_eq!(parsed.kind(), kind);
+        assert_eq!(parsed.name, name);
+        assert_eq!(parsed.identity.as_deref(), identity);
+        assert_eq!(parsed.version.as_deref(), number);
+        assert_eq!(parsed.to_string(), version);
+    }
+}
+
+#[test]
+fn web3_sha3() {
+    assert_method_serialization!(web3::Sha3 {
+        (hex!("68656c6c6f20776f726c64").to_vec(),) => json!(["0x68656c6c6f20776f726c64"]);
+        digest!("0x47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad")
+            => json!("0x47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad");
+    });
+}
+
+#[test]
+fn anvil_dump_state() {
+    assert_method_serialization!(anvil::DumpState {
+        Empty => json!([]);
+        hex!("1f8b08").to_vec() => json!("0x1f8b08");
+    });
+}
+
+#[test]
+fn anvil_mine() {
+    assert_method_serialization!(anvil::Mine {
+        (None, None) => json!([]);
+        () => json!(null);
+    });
+    assert_method_serialization!(anvil::Mine {
+        (Some(10), None) => json!(["0xa"]);
+        () => json!(null);
+    });
+    assert_method_serialization!(anvil::Mine {
+        (Some(10), Some(12)) => json!(["0xa", "0xc"]);
+        () => json!(null);
+    });
+}
+
+#[test]
+fn anvil_reset() {
+    assert_method_serialization!(anvil::Reset {
+        (None,) => json!([]);
+        () => json!(null);
+    });
+    assert_method_serialization!(anvil::Reset {
+        (Some(ResetConfig {
+            forking: Some(ForkConfig {
+                json_rpc_url: Some("https://eth.llamarpc.com".to_owned()),
+                block_number: Some(21_000_000),
+            }),
+        }),) => json!([{
+            "forking": {
+                "jsonRpcUrl": "https://eth.llamarpc.com",
+                "blockNumber": 21_000_000,
+            },
+        }]);
+        () => json!(null);
+    });
+}
+
+#[test]
+fn anvil_set_code() {
+    assert_method_serialization!(anvil::SetCode {
+        (
+            address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"),
+            hex!("6080").to_vec(),
+        ) => json!([
+            "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+            "0x6080",
+        ]);
+        () => json!(null);
+    });
+}
+
+#[test]
+fn anvil_set_nonce() {
+    assert_method_serialization!(anvil::SetNonce {
+        (address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"), 42) => json!([
+            "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+            "0x2a",
+        ]);
+        () => json!(null);
+    });
+}
+
+#[test]
+fn anvil_set_storage_at() {
+    assert_method_serialization!(anvil::SetStorageAt {
+        (
+            address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"),
+            uint!("0x0"),
+            digest!("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        ) => json!([
+            "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+            "0x0",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        ]);
+        true => json!(true);
+    });
+}
+
+#[test]
+fn evm_increase_time() {
+    assert_method_serialization!(evm::IncreaseTime {
+        (3600,) => json!(["0xe10"]);
+        jsonrpc::Value(json!(3600)) => json!(3600);
+    });
+}
+
+#[test]
+fn evm_snapshot() {
+    assert_method_serialization!(evm::Snapshot {
+        Empty => json!([]);
+        uint!("0x1") => json!("0x1");
+    });
+    assert_method_serialization!(evm::Revert {
+        (uint!("0x1"),) => json!(["0x1"]);
+        true => json!(true);
+    });
+}
+
+#[test]
+fn hardhat_set_balance() {
+    assert_method_serialization!(hardhat::SetBalance {
+        (
+            address!("0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13"),
+            uint!("0xde0b6b3a7640000"),
+        ) => json!([
+            "0x69498dd54BD25AA0c886cF1f8B8aE0856d55fF13",
+            "0xde0b6b3a7640000",
+        ]);
+        true => json!(true);
+    });
+}
+
+#[test]
+fn admin_add_peer() {
+    assert_method_serialization!(admin::AddPeer {
+        ("enode://a979fb575495b8d6db44f750317d0f4622bf4c2aa3365d6af7c284339968eef29b69ad0dce72a4d8db5ebb4968de0e3bec910127f134779fbcb0cb6d3331163c@52.16.188.185:30303".to_owned(),) => json!([
+            "enode://a979fb575495b8d6db44f750317d0f4622bf4c2aa3365d6af7c284339968eef29b69ad0dce72a4d8db5ebb4968de0e3bec910127f134779fbcb0cb6d3331163c@52.16.188.185:30303",
+        ]);
+        true => json!(true);
+    });
+}
+
+#[test]
+fn admin_datadir() {
+    assert_method_serialization!(admin::Datadir {
+        Empty => json!([]);
+        "/home/user/.ethereum".to_owned() => json!("/home/user/.ethereum");
+    });
+}
+
+#[test]
+fn admin_node_info() {
+    assert_method_serialization!(admin::NodeInfo {
+        Empty => json!([]);
+        NodeInfo {
+            id: "44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3".to_owned(),
+            name: ClientVersion {
+                name: "Geth".to_owned(),
+                identity: None,
+                version: Some("v1.14.11-stable-f3c696fa".to_owned()),
+                os: Some("linux-amd64".to_owned()),
+                language: Some("go1.23.2".to_owned()),
+            },
+            enode: "enode://44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3e7c5fba0b1d70aac5308426f47df2a128a6747040a3815cc7dd7167d03be320d@[::]:30303".to_owned(),
+            enr: Some("enr:-Jy4QGx1XoCr3t8yGNwZ8cp0yRSH5vuqFzLfL9sb2R2rkMDs_dC1ZGMBsRDgxAQwS9E5dc9xXsInCA1YpEM4UwCtPFY2Bg2V0aMfGhEmSYGOAgmlkgnY0gmlwhH8AAAGJc2VjcDI1NmsxoQNEgmpdalX4ihgpi8pHc_yldJzcOlyfMIqn2BDpsxEj84RzbmFwwIN0Y3CCdl-DdWRwgnZf".to_owned()),
+            ip: "127.0.0.1".to_owned(),
+            ports: NodePorts {
+                discovery: 30303,
+                listener: 30303,
+            },
+            listen_addr: "[::]:30303".to_owned(),
+            protocols: HashMap::from([
+                (
+                    "eth".to_owned(),
+                    NodeProtocol::Eth(EthProtocolInfo {
+                        network: 1,
+                        genesis: digest!("0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"),
+                        head: digest!("0x8c3ebbd9a9b8c8a8f1b5b0f1c25ba4c9e0a91f4b16a7d9bd0e6f8b1a5c7e2d3f"),
+                        extra: HashMap::from([
+                            ("difficulty".to_owned(), jsonrpc::Value(json!(17179869184_u64))),
+                            ("config".to_owned(), jsonrpc::Value(json!({ "chainId": 1 }))),
+                        ]),
+                    }),
+                ),
+                ("snap".to_owned(), NodeProtocol::Other(jsonrpc::Value(json!({})))),
+            ]),
+        } => json!({
+            "id": "44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3",
+            "name": "Geth/v1.14.11-stable-f3c696fa/linux-amd64/go1.23.2",
+            "enode": "enode://44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3e7c5fba0b1d70aac5308426f47df2a128a6747040a3815cc7dd7167d03be320d@[::]:30303",
+            "enr": "enr:-Jy4QGx1XoCr3t8yGNwZ8cp0yRSH5vuqFzLfL9sb2R2rkMDs_dC1ZGMBsRDgxAQwS9E5dc9xXsInCA1YpEM4UwCtPFY2Bg2V0aMfGhEmSYGOAgmlkgnY0gmlwhH8AAAGJc2VjcDI1NmsxoQNEgmpdalX4ihgpi8pHc_yldJzcOlyfMIqn2BDpsxEj84RzbmFwwIN0Y3CCdl-DdWRwgnZf",
+            "ip": "127.0.0.1",
+            "ports": {
+                "discovery": 30303,
+                "listener": 30303,
+            },
+            "listenAddr": "[::]:30303",
+            "protocols": {
+                "eth": {
+                    "network": 1,
+                    "genesis": "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+                    "head": "0x8c3ebbd9a9b8c8a8f1b5b0f1c25ba4c9e0a91f4b16a7d9bd0e6f8b1a5c7e2d3f",
+                    "difficulty": 17179869184_u64,
+                    "config": { "chainId": 1 },
+                },
+                "snap": {},
+            },
+        });
+    });
+}
+
+#[test]
+fn admin_peers() {
+    assert_method_serialization!(admin::Peers {
+        Empty => json!([]);
+        vec![PeerInfo {
+            id: "04f7a0ec7e0bc7a2da3e53a4c4cfdd8c52d1ccb5d7db1d0d3a5cd5e9cc1b1e0c".to_owned(),
+            name: ClientVersion {
+                name: "Nethermind".to_owned(),
+                identity: None,
+                version: Some("v1.29.1+dfea5240".to_owned()),
+                os: Some("linux-x64".to_owned()),
+                language: Some("dotnet8.0.10".to_owned()),
+            },
+            enode: "enode://e1b6e6e4b0c5bd6d4e0eb5a50c1bb4b4a1fa0d4da3aa7df0ccf1f20a1f3f5a5b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3@203.0.113.7:30303".to_owned(),
+            enr: None,
+            caps: vec!["eth/68".to_owned(), "snap/1".to_owned()],
+            network: PeerNetworkInfo {
+                local_address: "192.168.1.2:50504".to_owned(),
+                remote_address: "203.0.113.7:30303".to_owned(),
+                inbound: false,
+                trusted: false,
+                static_: true,
+            },
+            protocols: HashMap::from([
+                (
+                    "eth".to_owned(),
+                    PeerProtocol::Version {
+                        version: 68,
+                        extra: HashMap::from([(
+                            "difficulty".to_owned(),
+                            jsonrpc::Value(json!(17179869184_u64)),
+                        )]),
+                    },
+                ),
+                ("snap".to_owned(), PeerProtocol::Other(jsonrpc::Value(json!("handshake")))),
+            ]),
+        }] => json!([{
+            "id": "04f7a0ec7e0bc7a2da3e53a4c4cfdd8c52d1ccb5d7db1d0d3a5cd5e9cc1b1e0c",
+            "name": "Nethermind/v1.29.1+dfea5240/linux-x64/dotnet8.0.10",
+            "enode": "enode://e1b6e6e4b0c5bd6d4e0eb5a50c1bb4b4a1fa0d4da3aa7df0ccf1f20a1f3f5a5b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3@203.0.113.7:30303",
+            "caps": ["eth/68", "snap/1"],
+            "network": {
+                "localAddress": "192.168.1.2:50504",
+                "remoteAddress": "203.0.113.7:30303",
+                "inbound": false,
+                "trusted": false,
+                "static": true,
+            },
+            "protocols": {
+                "eth": { "version": 68, "difficulty": 17179869184_u64 },
+                "snap": "handshake",
+            },
+        }]);
+    });
+}
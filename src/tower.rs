@@ -0,0 +1,280 @@
+//! Integration with `tower` services and layers.
+//!
+//! This module allows JSON RPC transports to be composed with standard `tower`
+//! middleware (such as timeouts, concurrency limits or load shedding). It
+//! provides adapters in both directions:
+//!
+//! - [`ServiceClient`] is a JSON RPC [`Transport`] built from any service that
+//!   handles [`Request`]s and batches of `Vec<Request>`. It can be added to a
+//!   `tower` service stack with the [`ClientLayer`].
+//! - [`TransportService`] is a service built from any JSON RPC [`Transport`].
+//!
+//! When the `http` feature is enabled, the HTTP [`Client`](crate::http::Client)
+//! also implements the [`Service`] trait directly.
+
+use crate::{
+    jsonrpc::{self, JsonError, Request, Response, batch},
+    transport::Transport,
+};
+use std::{
+    future::{self, Future},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A boxed error type, as used by most `tower` middleware.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A boxed future returned by services in this module.
+pub type BoxFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + Send>>;
+
+/// A JSON RPC client around a `tower` service.
+///
+/// The service is cloned for each request, so cheaply cloneable services
+/// (such as ones using `tower::buffer::Buffer`) should be preferred.
+#[derive(Clone)]
+pub struct ServiceClient<S> {
+    service: S,
+}
+
+impl<S> ServiceClient<S> {
+    /// Creates a new client for the specified service.
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+
+    /// Returns a reference to the inner service.
+    pub fn inner(&self) -> &S {
+        &self.service
+    }
+}
+
+impl<S> ServiceClient<S>
+where
+    S: Clone,
+{
+    /// Waits for the service to be ready and then calls it.
+    async fn execute<R>(&self, request: R) -> Result<S::Response, Error>
+    where
+        S: Service<R>,
+        S::Error: Into<BoxError>,
+    {
+        let mut service = self.service.clone();
+        future::poll_fn(|cx| service.poll_ready(cx))
+            .await
+            .map_err(|err| Error::Service(err.into()))?;
+        service
+            .call(request)
+            .await
+            .map_err(|err| Error::Service(err.into()))
+    }
+}
+
+impl<S> Transport for ServiceClient<S>
+where
+    S: Service<Request, Response = Response>
+        + Service<Vec<Request>, Response = Vec<Response>>
+        + Clone
+        + Send
+        + Sync,
+    <S as Service<Request>>::Error: Into<BoxError>,
+    <S as Service<Request>>::Future: Send,
+    <S as Service<Vec<Request>>>::Error: Into<BoxError>,
+    <S as Service<Vec<Request>>>::Future: Send,
+{
+    type Error = Error;
+
+    async fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        self.execute(request).await
+    }
+
+    async fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        self.execute(requests).await
+    }
+}
+
+/// A layer that wraps a service in a [`ServiceClient`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClientLayer;
+
+impl<S> Layer<S> for ClientLayer {
+    type Service = ServiceClient<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        ServiceClient::new(service)
+    }
+}
+
+/// A `tower` service around a JSON RPC transport.
+///
+/// The service is always ready, as JSON RPC transports don't provide any
+/// back-pressure.
+pub struct TransportService<T> {
+    inner: Arc<T>,
+}
+
+impl<T> TransportService<T> {
+    /// Creates a new service for the specified transport.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Returns a reference to the inner transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> Clone for TransportService<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Service<Request> for TransportService<T>
+where
+    T: Transport + 'static,
+{
+    type Response = Response;
+    type Error = T::Error;
+    type Future = BoxFuture<Response, T::Error>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let inner = self.inner.clone();
+        Box::pin(async move { inner.roundtrip(request).await })
+    }
+}
+
+impl<T> Service<Vec<Request>> for TransportService<T>
+where
+    T: Transport + 'static,
+{
+    type Response = Vec<Response>;
+    type Error = T::Error;
+    type Future = BoxFuture<Vec<Response>, T::Error>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, requests: Vec<Request>) -> Self::Future {
+        let inner = self.inner.clone();
+        Box::pin(async move { inner.roundtrip_batch(requests).await })
+    }
+}
+
+#[cfg(feature = "http")]
+mod http {
+    use super::BoxFuture;
+    use crate::{
+        http::{Client, Error},
+        jsonrpc::{Request, Response},
+        transport::Transport as _,
+    };
+    use std::task::{Context, Poll};
+    use tower_service::Service;
+
+    impl Service<Request> for Client {
+        type Response = Response;
+        type Error = Error;
+        type Future = BoxFuture<Response, Error>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: Request) -> Self::Future {
+            let client = self.clone();
+            Box::pin(async move { client.roundtrip(request).await })
+        }
+    }
+
+    impl Service<Vec<Request>> for Client {
+        type Response = Vec<Response>;
+        type Error = Error;
+        type Future = BoxFuture<Vec<Response>, Error>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, requests: Vec<Request>) -> Self::Future {
+            let client = self.clone();
+            Box::pin(async move { client.roundtrip_batch(requests).await })
+        }
+    }
+}
+
+/// An error using a [`ServiceClient`].
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON error: {0}")]
+    Json(#[from] JsonError),
+    #[error("service error: {0}")]
+    Service(BoxError),
+    #[error(transparent)]
+    Rpc(#[from] jsonrpc::Error),
+    #[error(transparent)]
+    Batch(#[from] batch::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eth, mock::ready, server::Router, transport::TransportExt as _, types::Empty};
+
+    /// A service that always fails to become ready.
+    #[derive(Clone)]
+    struct Overloaded;
+
+    impl<R> Service<R> for Overloaded {
+        type Response = Response;
+        type Error = BoxError;
+        type Future = BoxFuture<Response, BoxError>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Err("overloaded".into()))
+        }
+
+        fn call(&mut self, _: R) -> Self::Future {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn transport_service_roundtrip() {
+        let mut router = Router::new();
+        router
+            .register(eth::BlockNumber, |_| Ok(42))
+            .register(eth::ChainId, |_| Ok(1));
+        let client = ClientLayer.layer(TransportService::new(router));
+
+        assert_eq!(ready(client.call_np(eth::BlockNumber)).unwrap(), 42);
+        assert_eq!(
+            ready(client.batch(((eth::ChainId, Empty), (eth::BlockNumber, Empty)))).unwrap(),
+            (1, 42),
+        );
+    }
+
+    #[test]
+    fn service_errors() {
+        let client = ServiceClient::new(Overloaded);
+        let request = Request::new(eth::BlockNumber, Empty).unwrap();
+        assert!(matches!(
+            ready(client.execute(request)),
+            Err(Error::Service(_)),
+        ));
+    }
+}
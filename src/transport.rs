@@ -0,0 +1,279 @@
+//! Module containing JSON RPC transport abstractions.
+//!
+//! Transports are responsible for sending JSON RPC requests to a node and
+//! receiving their responses. This allows code to be written generically over
+//! the underlying client (HTTP, WebSocket, IPC, etc.) as well as for clients to
+//! be composed.
+
+#[cfg(any(all(feature = "ipc", unix), feature = "ws"))]
+use crate::{
+    Subscription,
+    types::{Digest, Header, Log, LogFilter, SubscriptionKind},
+};
+use crate::{
+    jsonrpc::{
+        self, JsonError, Request, Response,
+        batch::{self, Batch},
+    },
+    method::Method,
+    types::Empty,
+};
+use serde::Serialize;
+#[cfg(any(all(feature = "ipc", unix), feature = "ws"))]
+use serde::de::DeserializeOwned;
+use std::{future::Future, sync::Arc};
+
+/// An asynchronous JSON RPC transport.
+pub trait Transport: Send + Sync {
+    /// The transport error type.
+    type Error: From<jsonrpc::Error>
+        + From<JsonError>
+        + From<batch::Error>
+        + std::error::Error
+        + Send
+        + Sync
+        + 'static;
+
+    /// Sends a JSON RPC request and returns its response.
+    fn roundtrip(
+        &self,
+        request: Request,
+    ) -> impl Future<Output = Result<Response, Self::Error>> + Send;
+
+    /// Sends a batch of JSON RPC requests and returns their responses.
+    ///
+    /// Note that the responses are not required to be in the same order as the
+    /// requests.
+    fn roundtrip_batch(
+        &self,
+        requests: Vec<Request>,
+    ) -> impl Future<Output = Result<Vec<Response>, Self::Error>> + Send;
+}
+
+/// A blocking JSON RPC transport.
+pub trait BlockingTransport {
+    /// The transport error type.
+    type Error: From<jsonrpc::Error>
+        + From<JsonError>
+        + From<batch::Error>
+        + std::error::Error
+        + Send
+        + Sync
+        + 'static;
+
+    /// Sends a JSON RPC request and returns its response.
+    fn roundtrip(&self, request: Request) -> Result<Response, Self::Error>;
+
+    /// Sends a batch of JSON RPC requests and returns their responses.
+    ///
+    /// Note that the responses are not required to be in the same order as the
+    /// requests.
+    fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error>;
+}
+
+/// Extension methods for executing typed JSON RPC calls over a [`Transport`].
+pub trait TransportExt: Transport {
+    /// Executes a JSON RPC call.
+    fn call<M>(
+        &self,
+        method: M,
+        params: M::Params,
+    ) -> impl Future<Output = Result<M::Result, Self::Error>> + Send
+    where
+        M: Method + Serialize + Send,
+        M::Params: Send,
+    {
+        jsonrpc::call_async(method, params, |request| self.roundtrip(request))
+    }
+
+    /// Executes a JSON RPC call with no parameters.
+    fn call_np<M>(&self, method: M) -> impl Future<Output = Result<M::Result, Self::Error>> + Send
+    where
+        M: Method<Params = Empty> + Serialize + Send,
+    {
+        jsonrpc::call_async(method, Empty, |request| self.roundtrip(request))
+    }
+
+    /// Executes a JSON RPC batch request.
+    fn batch<B>(&self, batch: B) -> impl Future<Output = Result<B::Values, Self::Error>> + Send
+    where
+        B: Batch + Send,
+    {
+        batch::call_async(batch, |requests| self.roundtrip_batch(requests))
+    }
+
+    /// Executes a JSON RPC batch request, returning individual JSON RPC results
+    /// for each batched requests. This allows fine-grained error handling
+    /// for individual methods.
+    fn try_batch<B>(&self, batch: B) -> impl Future<Output = Result<B::Results, Self::Error>> + Send
+    where
+        B: Batch + Send,
+    {
+        batch::try_call_async(batch, |requests| self.roundtrip_batch(requests))
+    }
+}
+
+impl<T> TransportExt for T where T: Transport + ?Sized {}
+
+/// A [`Transport`] that supports subscriptions to node events.
+#[cfg(any(all(feature = "ipc", unix), feature = "ws"))]
+pub trait PubSubTransport: Transport {
+    /// Subscribes to node events of the specified kind.
+    fn subscribe<T>(
+        &self,
+        kind: SubscriptionKind,
+    ) -> impl Future<Output = Result<Subscription<T, Self::Error>, Self::Error>> + Send
+    where
+        T: DeserializeOwned;
+
+    /// Subscribes to new block headers.
+    fn subscribe_new_heads(
+        &self,
+    ) -> impl Future<Output = Result<Subscription<Header, Self::Error>, Self::Error>> + Send {
+        self.subscribe(SubscriptionKind::NewHeads)
+    }
+
+    /// Subscribes to logs matching the specified filter.
+    fn subscribe_logs(
+        &self,
+        filter: LogFilter,
+    ) -> impl Future<Output = Result<Subscription<Log, Self::Error>, Self::Error>> + Send {
+        self.subscribe(SubscriptionKind::Logs(filter))
+    }
+
+    /// Subscribes to hashes of transactions that are added to the pending
+    /// state.
+    fn subscribe_new_pending_transactions(
+        &self,
+    ) -> impl Future<Output = Result<Subscription<Digest, Self::Error>, Self::Error>> + Send {
+        self.subscribe(SubscriptionKind::NewPendingTransactions)
+    }
+}
+
+/// Extension methods for executing typed JSON RPC calls over a
+/// [`BlockingTransport`].
+pub trait BlockingTransportExt: BlockingTransport {
+    /// Executes a JSON RPC call.
+    fn call<M>(&self, method: M, params: M::Params) -> Result<M::Result, Self::Error>
+    where
+        M: Method + Serialize,
+    {
+        jsonrpc::call(method, params, |request| self.roundtrip(request))
+    }
+
+    /// Executes a JSON RPC call with no parameters.
+    fn call_np<M>(&self, method: M) -> Result<M::Result, Self::Error>
+    where
+        M: Method<Params = Empty> + Serialize,
+    {
+        jsonrpc::call(method, Empty, |request| self.roundtrip(request))
+    }
+
+    /// Executes a JSON RPC batch request.
+    fn batch<B>(&self, batch: B) -> Result<B::Values, Self::Error>
+    where
+        B: Batch,
+    {
+        batch::call(batch, |requests| self.roundtrip_batch(requests))
+    }
+
+    /// Executes a JSON RPC batch request, returning individual JSON RPC results
+    /// for each batched requests. This allows fine-grained error handling
+    /// for individual methods.
+    fn try_batch<B>(&self, batch: B) -> Result<B::Results, Self::Error>
+    where
+        B: Batch,
+    {
+        batch::try_call(batch, |requests| self.roundtrip_batch(requests))
+    }
+}
+
+impl<T> BlockingTransportExt for T where T: BlockingTransport + ?Sized {}
+
+impl<T> Transport for &T
+where
+    T: Transport + ?Sized,
+{
+    type Error = T::Error;
+
+    fn roundtrip(
+        &self,
+        request: Request,
+    ) -> impl Future<Output = Result<Response, Self::Error>> + Send {
+        (**self).roundtrip(request)
+    }
+
+    fn roundtrip_batch(
+        &self,
+        requests: Vec<Request>,
+    ) -> impl Future<Output = Result<Vec<Response>, Self::Error>> + Send {
+        (**self).roundtrip_batch(requests)
+    }
+}
+
+impl<T> Transport for Arc<T>
+where
+    T: Transport + ?Sized,
+{
+    type Error = T::Error;
+
+    fn roundtrip(
+        &self,
+        request: Request,
+    ) -> impl Future<Output = Result<Response, Self::Error>> + Send {
+        (**self).roundtrip(request)
+    }
+
+    fn roundtrip_batch(
+        &self,
+        requests: Vec<Request>,
+    ) -> impl Future<Output = Result<Vec<Response>, Self::Error>> + Send {
+        (**self).roundtrip_batch(requests)
+    }
+}
+
+impl<T> BlockingTransport for &T
+where
+    T: BlockingTransport + ?Sized,
+{
+    type Error = T::Error;
+
+    fn roundtrip(&self, request: Request) -> Result<Response, Self::Error> {
+        (**self).roundtrip(request)
+    }
+
+    fn roundtrip_batch(&self, requests: Vec<Request>) -> Result<Vec<Response>, Self::Error> {
+        (**self).roundtrip_batch(requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eth, mock::Mock};
+
+    #[test]
+    fn extension_methods() {
+        let mut mock = Mock::new();
+        mock.expect(eth::BlockNumber).returns(42);
+        mock.expect(eth::ChainId).returns(1);
+
+        assert_eq!(
+            BlockingTransportExt::call(&mock, eth::BlockNumber, Empty).unwrap(),
+            42,
+        );
+        assert_eq!(
+            BlockingTransportExt::call_np(&mock, eth::BlockNumber).unwrap(),
+            42,
+        );
+        assert_eq!(
+            BlockingTransportExt::batch(&mock, ((eth::BlockNumber, Empty), (eth::ChainId, Empty)))
+                .unwrap(),
+            (42, 1),
+        );
+        assert!(matches!(
+            BlockingTransportExt::try_batch(&mock, ((eth::ChainId, Empty),)).unwrap(),
+            (Ok(1),),
+        ));
+    }
+}
@@ -1,15 +1,18 @@
 //! Ethereum RPC types.
 
-use crate::{debug, serialization};
+use crate::{hex, jsonrpc, serialization};
 use ethprim::AsU256 as _;
 use serde::{
     Deserialize, Serialize,
     de::{self, Deserializer},
-    ser::Serializer,
+    ser::{self, Serializer},
 };
 use std::{
-    collections::HashMap,
-    fmt::{self, Debug, Formatter},
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
 };
 
 pub use crate::bloom::Bloom;
@@ -152,6 +155,80 @@ impl FilterId {
     }
 }
 
+/// A subscription identifier.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SubscriptionId(String);
+
+impl SubscriptionId {
+    /// Creates a subscription from a raw ID. The caller must make sure that
+    /// this is a valid ID, otherwise subscription RPC methods will fail.
+    pub fn from_raw(value: String) -> Self {
+        Self(value)
+    }
+
+    /// Gets the raw underlying ID for the subscription.
+    pub fn into_raw(self) -> String {
+        self.0
+    }
+}
+
+/// The kind of events to subscribe to.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SubscriptionKind {
+    /// New block headers.
+    NewHeads,
+    /// Logs matching the specified filter.
+    Logs(LogFilter),
+    /// Hashes of transactions that are added to the pending state.
+    NewPendingTransactions,
+}
+
+impl Serialize for SubscriptionKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::NewHeads => ("newHeads",).serialize(serializer),
+            Self::Logs(filter) => ("logs", filter).serialize(serializer),
+            Self::NewPendingTransactions => ("newPendingTransactions",).serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SubscriptionKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        enum Kind {
+            NewHeads,
+            Logs,
+            NewPendingTransactions,
+        }
+
+        #[allow(clippy::large_enum_variant)]
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Filter((Kind, LogFilter)),
+            Kind((Kind,)),
+        }
+
+        match Value::deserialize(deserializer)? {
+            Value::Kind((Kind::NewHeads,)) => Ok(Self::NewHeads),
+            Value::Kind((Kind::Logs,)) => Ok(Self::Logs(LogFilter::default())),
+            Value::Kind((Kind::NewPendingTransactions,)) => Ok(Self::NewPendingTransactions),
+            Value::Filter((Kind::Logs, filter)) => Ok(Self::Logs(filter)),
+            Value::Filter(_) => Err(de::Error::custom("unexpected subscription parameters")),
+        }
+    }
+}
+
 /// Whether block transactions should be hydrated.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum Hydrated {
@@ -207,7 +284,7 @@ pub struct BlockNonce(pub [u8; 8]);
 impl Debug for BlockNonce {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.debug_tuple("BlockNonce")
-            .field(&debug::Hex(&self.0))
+            .field(&hex::Hex(&self.0))
             .finish()
     }
 }
@@ -240,6 +317,12 @@ pub enum BlockTransactions {
     Full(Vec<SignedTransaction>),
 }
 
+impl Default for BlockTransactions {
+    fn default() -> Self {
+        Self::Hash(Vec::new())
+    }
+}
+
 /// A signed transaction.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(tag = "type")]
@@ -359,7 +442,7 @@ impl Debug for SignedLegacyTransaction {
             .field("gas", &self.gas)
             .field("gas_price", &self.gas_price)
             .field("hash", &self.hash)
-            .field("input", &debug::Hex(&self.input))
+            .field("input", &hex::Hex(&self.input))
             .field("nonce", &self.nonce)
             .field("to", &self.to)
             .field("transaction_index", &self.transaction_index)
@@ -435,7 +518,7 @@ impl Debug for SignedEip2930Transaction {
             .field("gas", &self.gas)
             .field("gas_price", &self.gas_price)
             .field("hash", &self.hash)
-            .field("input", &debug::Hex(&self.input))
+            .field("input", &hex::Hex(&self.input))
             .field("nonce", &self.nonce)
             .field("to", &self.to)
             .field("transaction_index", &self.transaction_index)
@@ -516,7 +599,7 @@ impl Debug for SignedEip1559Transaction {
             .field("max_fee_per_gas", &self.max_fee_per_gas)
             .field("max_priority_fee_per_gas", &self.max_priority_fee_per_gas)
             .field("hash", &self.hash)
-            .field("input", &debug::Hex(&self.input))
+            .field("input", &hex::Hex(&self.input))
             .field("nonce", &self.nonce)
             .field("to", &self.to)
             .field("transaction_index", &self.transaction_index)
@@ -603,7 +686,7 @@ impl Debug for SignedEip4844Transaction {
             .field("max_priority_fee_per_gas", &self.max_priority_fee_per_gas)
             .field("max_fee_per_blob_gas", &self.max_fee_per_blob_gas)
             .field("hash", &self.hash)
-            .field("input", &debug::Hex(&self.input))
+            .field("input", &hex::Hex(&self.input))
             .field("nonce", &self.nonce)
             .field("to", &self.to)
             .field("transaction_index", &self.transaction_index)
@@ -689,7 +772,7 @@ impl Debug for SignedEip7702Transaction {
             .field("max_fee_per_gas", &self.max_fee_per_gas)
             .field("max_priority_fee_per_gas", &self.max_priority_fee_per_gas)
             .field("hash", &self.hash)
-            .field("input", &debug::Hex(&self.input))
+            .field("input", &hex::Hex(&self.input))
             .field("nonce", &self.nonce)
             .field("to", &self.to)
             .field("transaction_index", &self.transaction_index)
@@ -808,7 +891,7 @@ impl Debug for Block {
             .field("gas_limit", &self.gas_limit)
             .field("gas_used", &self.gas_used)
             .field("timestamp", &self.timestamp)
-            .field("extra_data", &debug::Hex(&self.extra_data))
+            .field("extra_data", &hex::Hex(&self.extra_data))
             .field("mix_hash", &self.mix_hash)
             .field("nonce", &self.nonce)
             .field("base_fee_per_gas", &self.base_fee_per_gas)
@@ -825,6 +908,97 @@ impl Debug for Block {
     }
 }
 
+/// An Ethereum block header object, such as the ones received from `newHeads`
+/// subscriptions.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Header {
+    /// The block hash.
+    pub hash: Digest,
+    /// The parent block hash.
+    pub parent_hash: Digest,
+    /// The Ommer's hash.
+    pub sha3_uncles: Digest,
+    /// The coinbase. This is the address that received the block rewards.
+    pub miner: Address,
+    /// The state root.
+    pub state_root: Digest,
+    /// The transactions root.
+    pub transactions_root: Digest,
+    /// The transaction receipts root.
+    pub receipts_root: Digest,
+    /// The log bloom filter.
+    pub logs_bloom: Bloom,
+    /// The difficulty.
+    pub difficulty: U256,
+    /// The block height.
+    #[serde(with = "serialization::num")]
+    pub number: u64,
+    /// The gas limit.
+    #[serde(with = "serialization::num")]
+    pub gas_limit: u64,
+    /// The total gas used by all transactions.
+    #[serde(with = "serialization::num")]
+    pub gas_used: u64,
+    /// The timestamp (in second).
+    #[serde(with = "serialization::num")]
+    pub timestamp: u64,
+    /// Extra data.
+    #[serde(with = "serialization::bytes")]
+    pub extra_data: Vec<u8>,
+    /// The mix hash.
+    pub mix_hash: Digest,
+    /// The nonce.
+    pub nonce: BlockNonce,
+    /// The base fee per gas.
+    #[serde(default)]
+    pub base_fee_per_gas: U256,
+    /// The withdrawals root.
+    #[serde(default)]
+    pub withdrawals_root: Digest,
+    /// Blob gas used.
+    #[serde(default, with = "serialization::num")]
+    pub blob_gas_used: u64,
+    /// Excess blob gas.
+    #[serde(default, with = "serialization::num")]
+    pub excess_blob_gas: u64,
+    /// Parent beacon block root.
+    #[serde(default)]
+    pub parent_beacon_block_root: Digest,
+    /// EIP-7685 requests hash.
+    #[serde(default)]
+    pub requests_hash: Digest,
+}
+
+impl Debug for Header {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Header")
+            .field("hash", &self.hash)
+            .field("parent_hash", &self.parent_hash)
+            .field("sha3_uncles", &self.sha3_uncles)
+            .field("miner", &self.miner)
+            .field("state_root", &self.state_root)
+            .field("transactions_root", &self.transactions_root)
+            .field("receipts_root", &self.receipts_root)
+            .field("logs_bloom", &self.logs_bloom)
+            .field("difficulty", &self.difficulty)
+            .field("number", &self.number)
+            .field("gas_limit", &self.gas_limit)
+            .field("gas_used", &self.gas_used)
+            .field("timestamp", &self.timestamp)
+            .field("extra_data", &hex::Hex(&self.extra_data))
+            .field("mix_hash", &self.mix_hash)
+            .field("nonce", &self.nonce)
+            .field("base_fee_per_gas", &self.base_fee_per_gas)
+            .field("withdrawals_root", &self.withdrawals_root)
+            .field("blob_gas_used", &self.blob_gas_used)
+            .field("excess_blob_gas", &self.excess_blob_gas)
+            .field("parent_beacon_block_root", &self.parent_beacon_block_root)
+            .field("requests_hash", &self.requests_hash)
+            .finish()
+    }
+}
+
 /// An Ethereum transaction object.
 #[derive(Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -915,7 +1089,7 @@ impl Debug for Transaction {
             .field("from", &self.from)
             .field("gas", &self.gas)
             .field("value", &self.value)
-            .field("input", &self.input.as_deref().map(debug::Hex))
+            .field("input", &self.input.as_deref().map(hex::Hex))
             .field("gas_price", &self.gas_price)
             .field("max_priority_fee_per_gas", &self.max_priority_fee_per_gas)
             .field("max_fee_per_gas", &self.max_fee_per_gas)
@@ -923,7 +1097,7 @@ impl Debug for Transaction {
             .field("access_list", &self.access_list)
             .field("authorization_list", &self.authorization_list)
             .field("blob_versioned_hashes", &self.blob_versioned_hashes)
-            .field("blobs", &self.blobs.as_deref().map(debug::HexSlice))
+            .field("blobs", &self.blobs.as_deref().map(hex::HexSlice))
             .field("chain_id", &self.chain_id)
             .finish()
     }
@@ -1120,7 +1294,7 @@ pub struct CallResultSuccess {
 impl Debug for CallResultSuccess {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.debug_struct("CallResultSuccess")
-            .field("return_data", &debug::Hex(&self.return_data))
+            .field("return_data", &hex::Hex(&self.return_data))
             .field("gas_used", &self.gas_used)
             .field("logs", &self.logs)
             .finish()
@@ -1144,7 +1318,7 @@ pub struct CallResultFailure {
 impl Debug for CallResultFailure {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.debug_struct("SimulatedCallFailure")
-            .field("return_data", &debug::Hex(&self.return_data))
+            .field("return_data", &hex::Hex(&self.return_data))
             .field("gas_used", &self.gas_used)
             .field("error", &self.error)
             .finish()
@@ -1253,7 +1427,7 @@ impl Debug for ForkConfiguration {
             .field("activation_time", &self.activation_time)
             .field("blob_schedule", &self.blob_schedule)
             .field("chain_id", &self.chain_id)
-            .field("fork_id", &debug::Hex(&self.fork_id))
+            .field("fork_id", &hex::Hex(&self.fork_id))
             .field("precompiles", &self.precompiles)
             .field("system_contracts", &self.system_contracts)
             .finish()
@@ -1337,7 +1511,7 @@ impl Debug for AccountOverrides {
         f.debug_struct("AccountOverrides")
             .field("balance", &self.balance)
             .field("nonce", &self.nonce)
-            .field("code", &self.code.as_deref().map(debug::Hex))
+            .field("code", &self.code.as_deref().map(hex::Hex))
             .field("state", &self.state)
             .field("state_diff", &self.state_diff)
             .field(
@@ -1457,7 +1631,7 @@ impl Debug for StorageProof {
         f.debug_struct("StorageProof")
             .field("key", &self.key)
             .field("value", &self.value)
-            .field("proof", &debug::HexSlice(&self.proof))
+            .field("proof", &hex::HexSlice(&self.proof))
             .finish()
     }
 }
@@ -1488,7 +1662,7 @@ impl Debug for AccountProof {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.debug_struct("AccountProof")
             .field("address", &self.address)
-            .field("account_proof", &debug::HexSlice(&self.account_proof))
+            .field("account_proof", &hex::HexSlice(&self.account_proof))
             .field("balance", &self.balance)
             .field("code_hash", &self.code_hash)
             .field("nonce", &self.nonce)
@@ -1498,6 +1672,21 @@ impl Debug for AccountProof {
     }
 }
 
+/// The state of an account.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    /// Balance.
+    pub balance: U256,
+    /// Code hash.
+    pub code_hash: Digest,
+    /// Nonce.
+    #[serde(with = "serialization::num")]
+    pub nonce: u64,
+    /// Storage root.
+    pub storage_root: Digest,
+}
+
 /// The storage slots requested per account.
 pub type StorageRequests = HashMap<Address, Vec<Digest>>;
 
@@ -1641,7 +1830,7 @@ impl Debug for Log {
             .field("block_number", &self.block_number)
             .field("block_timestamp", &self.block_timestamp)
             .field("address", &self.address)
-            .field("data", &debug::Hex(&self.data))
+            .field("data", &hex::Hex(&self.data))
             .field("topics", &self.topics)
             .finish()
     }
@@ -1756,3 +1945,1576 @@ impl Debug for TransactionReceipt {
             .finish()
     }
 }
+
+/// An execution payload, as exchanged with the consensus layer over the Engine
+/// API.
+///
+/// Fields that were added in later versions of the payload are optional and
+/// omitted when not set.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionPayload {
+    /// The parent block hash.
+    pub parent_hash: Digest,
+    /// The address that receives the priority fees of the block.
+    pub fee_recipient: Address,
+    /// The state root.
+    pub state_root: Digest,
+    /// The transaction receipts root.
+    pub receipts_root: Digest,
+    /// The log bloom filter.
+    pub logs_bloom: Bloom,
+    /// The randomness value from the beacon chain.
+    pub prev_randao: Digest,
+    /// The block height.
+    #[serde(with = "serialization::num")]
+    pub block_number: u64,
+    /// The gas limit.
+    #[serde(with = "serialization::num")]
+    pub gas_limit: u64,
+    /// The total gas used by all transactions.
+    #[serde(with = "serialization::num")]
+    pub gas_used: u64,
+    /// The timestamp (in seconds).
+    #[serde(with = "serialization::num")]
+    pub timestamp: u64,
+    /// Extra data.
+    #[serde(with = "serialization::bytes")]
+    pub extra_data: Vec<u8>,
+    /// The base fee per gas.
+    pub base_fee_per_gas: U256,
+    /// The block hash.
+    pub block_hash: Digest,
+    /// The RLP encoded transactions.
+    #[serde(with = "serialization::vec_bytes")]
+    pub transactions: Vec<Vec<u8>>,
+    /// Withdrawals. Added in `ExecutionPayloadV2`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Blob gas used. Added in `ExecutionPayloadV3`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serialization::option_num"
+    )]
+    pub blob_gas_used: Option<u64>,
+    /// Excess blob gas. Added in `ExecutionPayloadV3`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serialization::option_num"
+    )]
+    pub excess_blob_gas: Option<u64>,
+}
+
+impl Debug for ExecutionPayload {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ExecutionPayload")
+            .field("parent_hash", &self.parent_hash)
+            .field("fee_recipient", &self.fee_recipient)
+            .field("state_root", &self.state_root)
+            .field("receipts_root", &self.receipts_root)
+            .field("logs_bloom", &self.logs_bloom)
+            .field("prev_randao", &self.prev_randao)
+            .field("block_number", &self.block_number)
+            .field("gas_limit", &self.gas_limit)
+            .field("gas_used", &self.gas_used)
+            .field("timestamp", &self.timestamp)
+            .field("extra_data", &hex::Hex(&self.extra_data))
+            .field("base_fee_per_gas", &self.base_fee_per_gas)
+            .field("block_hash", &self.block_hash)
+            .field("transactions", &hex::HexSlice(&self.transactions))
+            .field("withdrawals", &self.withdrawals)
+            .field("blob_gas_used", &self.blob_gas_used)
+            .field("excess_blob_gas", &self.excess_blob_gas)
+            .finish()
+    }
+}
+
+/// The fork choice state of the consensus layer.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkchoiceState {
+    /// The hash of the head of the canonical chain.
+    pub head_block_hash: Digest,
+    /// The hash of the most recent safe block.
+    pub safe_block_hash: Digest,
+    /// The hash of the most recent finalized block.
+    pub finalized_block_hash: Digest,
+}
+
+/// Attributes for building a new payload on top of the fork choice head.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadAttributes {
+    /// The timestamp of the payload (in seconds).
+    #[serde(with = "serialization::num")]
+    pub timestamp: u64,
+    /// The randomness value from the beacon chain.
+    pub prev_randao: Digest,
+    /// The address that receives the priority fees of the block.
+    pub suggested_fee_recipient: Address,
+    /// Withdrawals. Added in `PayloadAttributesV2`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Parent beacon block root. Added in `PayloadAttributesV3`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<Digest>,
+}
+
+/// The result of validating an execution payload.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadStatus {
+    /// The validation status.
+    pub status: PayloadStatusKind,
+    /// The hash of the most recent valid block in the branch defined by the
+    /// payload and its ancestors.
+    pub latest_valid_hash: Option<Digest>,
+    /// A message describing why the payload is invalid.
+    pub validation_error: Option<String>,
+}
+
+/// The validation status of an execution payload.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayloadStatusKind {
+    /// The payload is valid.
+    Valid,
+    /// The payload is invalid.
+    Invalid,
+    /// The payload could not be validated because the node is syncing.
+    Syncing,
+    /// The payload was accepted but not yet validated because it extends a
+    /// side chain.
+    Accepted,
+    /// The block hash of the payload is invalid.
+    InvalidBlockHash,
+}
+
+/// The result of updating the fork choice state.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkchoiceUpdatedResult {
+    /// The status of the fork choice head.
+    pub payload_status: PayloadStatus,
+    /// The identifier of the payload build process that was started, if
+    /// payload attributes were specified.
+    pub payload_id: Option<PayloadId>,
+}
+
+/// An identifier of a payload build process.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct PayloadId(pub [u8; 8]);
+
+impl Debug for PayloadId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("PayloadId")
+            .field(&hex::Hex(&self.0))
+            .finish()
+    }
+}
+
+impl Serialize for PayloadId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialization::bytearray::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PayloadId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        serialization::bytearray::deserialize(deserializer).map(Self)
+    }
+}
+
+/// A built execution payload along with its value and associated data.
+///
+/// Fields that were added in later versions of the envelope are optional and
+/// omitted when not set.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionPayloadEnvelope {
+    /// The execution payload.
+    pub execution_payload: ExecutionPayload,
+    /// The expected value to be received by the fee recipient in wei.
+    pub block_value: U256,
+    /// The blobs, commitments and proofs for the blob transactions in the
+    /// payload. Added in `engine_getPayloadV3`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blobs_bundle: Option<BlobsBundle>,
+    /// Whether the consensus layer should prefer this payload over one from an
+    /// external builder. Added in `engine_getPayloadV3`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub should_override_builder: Option<bool>,
+    /// The EIP-7685 execution layer requests. Added in `engine_getPayloadV4`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serialization::option_vec_bytes"
+    )]
+    pub execution_requests: Option<Vec<Vec<u8>>>,
+}
+
+impl Debug for ExecutionPayloadEnvelope {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ExecutionPayloadEnvelope")
+            .field("execution_payload", &self.execution_payload)
+            .field("block_value", &self.block_value)
+            .field("blobs_bundle", &self.blobs_bundle)
+            .field("should_override_builder", &self.should_override_builder)
+            .field(
+                "execution_requests",
+                &self.execution_requests.as_deref().map(hex::HexSlice),
+            )
+            .finish()
+    }
+}
+
+/// The blobs of the blob transactions in a payload, along with their KZG
+/// commitments and proofs.
+#[derive(Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobsBundle {
+    /// The KZG commitments.
+    #[serde(with = "serialization::vec_bytes")]
+    pub commitments: Vec<Vec<u8>>,
+    /// The KZG proofs.
+    #[serde(with = "serialization::vec_bytes")]
+    pub proofs: Vec<Vec<u8>>,
+    /// The blobs.
+    #[serde(with = "serialization::vec_bytes")]
+    pub blobs: Vec<Vec<u8>>,
+}
+
+impl Debug for BlobsBundle {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("BlobsBundle")
+            .field("commitments", &hex::HexSlice(&self.commitments))
+            .field("proofs", &hex::HexSlice(&self.proofs))
+            .field("blobs", &hex::HexSlice(&self.blobs))
+            .finish()
+    }
+}
+
+/// A blob and its KZG proof.
+#[derive(Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobAndProof {
+    /// The blob.
+    #[serde(with = "serialization::bytes")]
+    pub blob: Vec<u8>,
+    /// The KZG proof.
+    #[serde(with = "serialization::bytes")]
+    pub proof: Vec<u8>,
+}
+
+impl Debug for BlobAndProof {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("BlobAndProof")
+            .field("blob", &hex::Hex(&self.blob))
+            .field("proof", &hex::Hex(&self.proof))
+            .finish()
+    }
+}
+
+/// Options for tracing transactions with the `debug_trace*` methods.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TraceConfig {
+    /// The tracer to use.
+    pub tracer: Tracer,
+    /// Timeout for the tracer (for example `"5s"`).
+    pub timeout: Option<String>,
+    /// State overrides to apply before tracing. Only used by
+    /// `debug_traceCall`.
+    pub state_overrides: Option<StateOverrides>,
+    /// Block overrides to apply before tracing. Only used by
+    /// `debug_traceCall`.
+    pub block_overrides: Option<BlockOverrides>,
+}
+
+/// A tracer used by the `debug_trace*` methods.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Tracer {
+    /// The default struct (opcode) logger.
+    StructLogger(StructLoggerConfig),
+    /// The built-in `callTracer`.
+    Call(CallTracerConfig),
+    /// The built-in `prestateTracer`.
+    Prestate(PrestateTracerConfig),
+    /// The built-in `4byteTracer`.
+    FourByte,
+    /// A custom JavaScript tracer, or a named tracer that is not built-in.
+    Custom {
+        /// The JavaScript code or name of the tracer.
+        code: String,
+        /// The tracer configuration.
+        config: Option<jsonrpc::Value>,
+    },
+}
+
+impl Default for Tracer {
+    fn default() -> Self {
+        Self::StructLogger(Default::default())
+    }
+}
+
+/// Configuration for the struct logger.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLoggerConfig {
+    /// Whether or not to include memory in the logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_memory: Option<bool>,
+    /// Whether or not to exclude the stack from the logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_stack: Option<bool>,
+    /// Whether or not to exclude storage from the logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_storage: Option<bool>,
+    /// Whether or not to include return data in the logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_return_data: Option<bool>,
+}
+
+/// Configuration for the `callTracer`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTracerConfig {
+    /// Whether or not to only trace the top-level call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub only_top_call: Option<bool>,
+    /// Whether or not to include logs emitted by calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_log: Option<bool>,
+}
+
+/// Configuration for the `prestateTracer`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateTracerConfig {
+    /// Whether or not to return the state before and after execution instead
+    /// of just the state before.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_mode: Option<bool>,
+    /// Whether or not to exclude account code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_code: Option<bool>,
+    /// Whether or not to exclude account storage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_storage: Option<bool>,
+}
+
+const CALL_TRACER: &str = "callTracer";
+const PRESTATE_TRACER: &str = "prestateTracer";
+const FOUR_BYTE_TRACER: &str = "4byteTracer";
+
+impl Serialize for TraceConfig {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        #[serde(untagged)]
+        enum Config<'a> {
+            Call(&'a CallTracerConfig),
+            Prestate(&'a PrestateTracerConfig),
+            Custom(&'a jsonrpc::Value),
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Value<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            tracer: Option<&'a str>,
+            #[serde(flatten)]
+            logger: Option<&'a StructLoggerConfig>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            tracer_config: Option<Config<'a>>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            timeout: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            state_overrides: Option<&'a StateOverrides>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            block_overrides: Option<&'a BlockOverrides>,
+        }
+
+        let (tracer, logger, tracer_config) = match &self.tracer {
+            Tracer::StructLogger(config) => (None, Some(config), None),
+            Tracer::Call(config) => (Some(CALL_TRACER), None, Some(Config::Call(config))),
+            Tracer::Prestate(config) => {
+                (Some(PRESTATE_TRACER), None, Some(Config::Prestate(config)))
+            }
+            Tracer::FourByte => (Some(FOUR_BYTE_TRACER), None, None),
+            Tracer::Custom { code, config } => (
+                Some(code.as_str()),
+                None,
+                config.as_ref().map(Config::Custom),
+            ),
+        };
+
+        Value {
+            tracer,
+            logger,
+            tracer_config,
+            timeout: self.timeout.as_deref(),
+            state_overrides: self.state_overrides.as_ref(),
+            block_overrides: self.block_overrides.as_ref(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TraceConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Value {
+            tracer: Option<String>,
+            #[serde(flatten)]
+            logger: StructLoggerConfig,
+            tracer_config: Option<jsonrpc::Value>,
+            timeout: Option<String>,
+            state_overrides: Option<StateOverrides>,
+            block_overrides: Option<BlockOverrides>,
+        }
+
+        fn config<T, E>(value: Option<jsonrpc::Value>) -> Result<T, E>
+        where
+            T: Default + de::DeserializeOwned,
+            E: de::Error,
+        {
+            match value {
+                Some(value) => serde_json::from_value(value.0).map_err(E::custom),
+                None => Ok(T::default()),
+            }
+        }
+
+        let value = Value::deserialize(deserializer)?;
+        let tracer = match value.tracer.as_deref() {
+            None => Tracer::StructLogger(value.logger),
+            Some(CALL_TRACER) => Tracer::Call(config(value.tracer_config)?),
+            Some(PRESTATE_TRACER) => Tracer::Prestate(config(value.tracer_config)?),
+            Some(FOUR_BYTE_TRACER) => Tracer::FourByte,
+            Some(code) => Tracer::Custom {
+                code: code.to_owned(),
+                config: value.tracer_config,
+            },
+        };
+
+        Ok(Self {
+            tracer,
+            timeout: value.timeout,
+            state_overrides: value.state_overrides,
+            block_overrides: value.block_overrides,
+        })
+    }
+}
+
+/// The result of tracing a transaction.
+///
+/// Note that the tracer is not known when decoding results, so results are
+/// decoded based on their shape. This means that results of custom tracers
+/// that happen to have the same shape as a built-in tracer result are decoded
+/// as such.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Trace {
+    /// Struct logger result.
+    StructLogs(StructLogTrace),
+    /// `callTracer` result.
+    Call(CallFrame),
+    /// `prestateTracer` result in diff mode.
+    PrestateDiff(PrestateDiffTrace),
+    /// `prestateTracer` result.
+    Prestate(PrestateTrace),
+    /// `4byteTracer` result.
+    FourByte(FourByteTrace),
+    /// Custom tracer result.
+    Custom(jsonrpc::Value),
+}
+
+/// The result of tracing a transaction in a block.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTrace {
+    /// The hash of the traced transaction.
+    pub tx_hash: Digest,
+    /// The trace result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Trace>,
+    /// The error that occurred while tracing the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The result of the struct logger.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogTrace {
+    /// The total gas used.
+    pub gas: u64,
+    /// Whether or not the execution failed.
+    pub failed: bool,
+    /// The hex-encoded return value. Note that some clients omit the `0x`
+    /// prefix.
+    pub return_value: String,
+    /// The executed opcodes.
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// An executed opcode logged by the struct logger.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    /// The program counter.
+    pub pc: u64,
+    /// The opcode name.
+    pub op: String,
+    /// The remaining gas.
+    pub gas: u64,
+    /// The gas cost of the opcode.
+    pub gas_cost: u64,
+    /// The call depth.
+    pub depth: u64,
+    /// The stack, if enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<U256>>,
+    /// The memory as hex-encoded 32-byte words, if enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<String>>,
+    /// The hex-encoded storage slots accessed so far, if enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<HashMap<String, String>>,
+    /// The gas refund counter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund: Option<u64>,
+    /// The error that occurred executing the opcode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A call frame traced by the `callTracer`.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// The kind of call.
+    #[serde(rename = "type")]
+    pub kind: CallKind,
+    /// The caller.
+    pub from: Address,
+    /// The callee ([`None`] for failed contract creations).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// The value transferred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    /// The gas provided to the call.
+    #[serde(with = "serialization::num")]
+    pub gas: u64,
+    /// The gas used by the call.
+    #[serde(with = "serialization::num")]
+    pub gas_used: u64,
+    /// The call data.
+    #[serde(with = "serialization::bytes")]
+    pub input: Vec<u8>,
+    /// The return data.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serialization::option_bytes"
+    )]
+    pub output: Option<Vec<u8>>,
+    /// The error, if the call failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The decoded revert reason, if the call reverted with one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// The logs emitted by the call. Only included when the tracer is
+    /// configured with `withLog`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logs: Vec<CallLog>,
+    /// The nested calls.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+impl Debug for CallFrame {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CallFrame")
+            .field("kind", &self.kind)
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("value", &self.value)
+            .field("gas", &self.gas)
+            .field("gas_used", &self.gas_used)
+            .field("input", &hex::Hex(&self.input))
+            .field("output", &self.output.as_deref().map(hex::Hex))
+            .field("error", &self.error)
+            .field("revert_reason", &self.revert_reason)
+            .field("logs", &self.logs)
+            .field("calls", &self.calls)
+            .finish()
+    }
+}
+
+/// The kind of a traced call.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CallKind {
+    /// A regular call.
+    Call,
+    /// A static call.
+    StaticCall,
+    /// A delegate call.
+    DelegateCall,
+    /// A legacy call code.
+    CallCode,
+    /// A contract creation.
+    Create,
+    /// A contract creation with a deterministic address.
+    Create2,
+    /// A self-destruct.
+    SelfDestruct,
+}
+
+/// A log emitted by a call frame.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallLog {
+    /// The address of the contract that emitted the log.
+    pub address: Address,
+    /// The topics emitted with the log.
+    pub topics: ArrayVec<Digest, 4>,
+    /// The data emitted with the log.
+    #[serde(with = "serialization::bytes")]
+    pub data: Vec<u8>,
+    /// The position of the log relative to the nested calls of the frame.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serialization::option_num"
+    )]
+    pub position: Option<u64>,
+}
+
+impl Debug for CallLog {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CallLog")
+            .field("address", &self.address)
+            .field("topics", &self.topics)
+            .field("data", &hex::Hex(&self.data))
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+/// The state of accounts touched by a transaction, as traced by the
+/// `prestateTracer`.
+pub type PrestateTrace = HashMap<Address, PrestateAccount>;
+
+/// The state of accounts touched by a transaction before and after its
+/// execution, as traced by the `prestateTracer` in diff mode.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PrestateDiffTrace {
+    /// The state before execution.
+    pub pre: PrestateTrace,
+    /// The modified state after execution.
+    pub post: PrestateTrace,
+}
+
+/// The state of an account traced by the `prestateTracer`.
+#[derive(Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    /// The account balance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// The account nonce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    /// The account code.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serialization::option_bytes"
+    )]
+    pub code: Option<Vec<u8>>,
+    /// The account storage slots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<HashMap<Digest, Digest>>,
+}
+
+impl Debug for PrestateAccount {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("PrestateAccount")
+            .field("balance", &self.balance)
+            .field("nonce", &self.nonce)
+            .field("code", &self.code.as_deref().map(hex::Hex))
+            .field("storage", &self.storage)
+            .finish()
+    }
+}
+
+/// The number of calls per function selector and call data size, as traced
+/// by the `4byteTracer`. Keys are formatted as `{selector}-{size}`.
+pub type FourByteTrace = HashMap<String, u64>;
+
+/// The kind of trace to produce with the `trace_*` replay methods.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceType {
+    /// A trace of the actions (calls, creations, self-destructs) performed.
+    Trace,
+    /// A full virtual machine execution trace.
+    VmTrace,
+    /// The state changes of the accounts touched.
+    StateDiff,
+}
+
+/// A filter for `trace_filter`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+    /// The first block to include traces from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockSpec>,
+    /// The last block to include traces from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockSpec>,
+    /// Only include traces from these addresses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<Vec<Address>>,
+    /// Only include traces to these addresses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<Vec<Address>>,
+    /// The number of matching traces to skip.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<u64>,
+    /// The maximum number of traces to return.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+/// An action trace of a transaction, as returned by the `trace_*` methods.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionTrace {
+    /// The action that was performed.
+    #[serde(flatten)]
+    pub action: TraceAction,
+    /// The result of the action, or [`None`] if it failed.
+    pub result: Option<TraceOutput>,
+    /// The error, if the action failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The number of direct child traces.
+    pub subtraces: u64,
+    /// The position of the trace in the call tree.
+    pub trace_address: Vec<u64>,
+}
+
+/// An action trace along with the block and transaction it was included in.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedTrace {
+    /// The action trace.
+    #[serde(flatten)]
+    pub trace: ActionTrace,
+    /// The hash of the block containing the transaction.
+    pub block_hash: Digest,
+    /// The height of the block containing the transaction.
+    pub block_number: u64,
+    /// The hash of the transaction ([`None`] for block rewards).
+    pub transaction_hash: Option<Digest>,
+    /// The index of the transaction within the block ([`None`] for block
+    /// rewards).
+    pub transaction_position: Option<u64>,
+}
+
+/// An action performed during the execution of a transaction.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "action", rename_all = "camelCase")]
+pub enum TraceAction {
+    /// A call.
+    Call(CallAction),
+    /// A contract creation.
+    Create(CreateAction),
+    /// A self-destruct.
+    #[serde(rename = "suicide")]
+    SelfDestruct(SelfDestructAction),
+    /// A block or uncle reward.
+    Reward(RewardAction),
+}
+
+/// A call action.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallAction {
+    /// The caller.
+    pub from: Address,
+    /// The callee.
+    pub to: Address,
+    /// The value transferred.
+    pub value: U256,
+    /// The gas provided to the call.
+    #[serde(with = "serialization::num")]
+    pub gas: u64,
+    /// The call data.
+    #[serde(with = "serialization::bytes")]
+    pub input: Vec<u8>,
+    /// The kind of call.
+    pub call_type: CallActionKind,
+}
+
+impl Debug for CallAction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CallAction")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("value", &self.value)
+            .field("gas", &self.gas)
+            .field("input", &hex::Hex(&self.input))
+            .field("call_type", &self.call_type)
+            .finish()
+    }
+}
+
+/// The kind of a call action.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallActionKind {
+    /// A regular call.
+    Call,
+    /// A static call.
+    StaticCall,
+    /// A delegate call.
+    DelegateCall,
+    /// A legacy call code.
+    CallCode,
+}
+
+/// A contract creation action.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAction {
+    /// The creator.
+    pub from: Address,
+    /// The value transferred to the new contract.
+    pub value: U256,
+    /// The gas provided to the creation.
+    #[serde(with = "serialization::num")]
+    pub gas: u64,
+    /// The contract init code.
+    #[serde(with = "serialization::bytes")]
+    pub init: Vec<u8>,
+}
+
+impl Debug for CreateAction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CreateAction")
+            .field("from", &self.from)
+            .field("value", &self.value)
+            .field("gas", &self.gas)
+            .field("init", &hex::Hex(&self.init))
+            .finish()
+    }
+}
+
+/// A self-destruct action.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfDestructAction {
+    /// The self-destructed contract.
+    pub address: Address,
+    /// The address receiving the contract's balance.
+    pub refund_address: Address,
+    /// The balance of the contract.
+    pub balance: U256,
+}
+
+/// A block or uncle reward action.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardAction {
+    /// The address receiving the reward.
+    pub author: Address,
+    /// The reward amount.
+    pub value: U256,
+    /// The kind of reward.
+    pub reward_type: RewardKind,
+}
+
+/// The kind of a reward action.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RewardKind {
+    /// A block reward.
+    Block,
+    /// An uncle reward.
+    Uncle,
+}
+
+/// The output of a successful action.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TraceOutput {
+    /// The output of a contract creation.
+    Create(CreateOutput),
+    /// The output of a call.
+    Call(CallOutput),
+}
+
+/// The output of a call action.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallOutput {
+    /// The gas used by the call.
+    #[serde(with = "serialization::num")]
+    pub gas_used: u64,
+    /// The return data.
+    #[serde(with = "serialization::bytes")]
+    pub output: Vec<u8>,
+}
+
+impl Debug for CallOutput {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CallOutput")
+            .field("gas_used", &self.gas_used)
+            .field("output", &hex::Hex(&self.output))
+            .finish()
+    }
+}
+
+/// The output of a contract creation action.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOutput {
+    /// The address of the created contract.
+    pub address: Address,
+    /// The deployed contract code.
+    #[serde(with = "serialization::bytes")]
+    pub code: Vec<u8>,
+    /// The gas used by the creation.
+    #[serde(with = "serialization::num")]
+    pub gas_used: u64,
+}
+
+impl Debug for CreateOutput {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CreateOutput")
+            .field("address", &self.address)
+            .field("code", &hex::Hex(&self.code))
+            .field("gas_used", &self.gas_used)
+            .finish()
+    }
+}
+
+/// The traces produced by replaying a transaction.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceResults {
+    /// The return data of the transaction.
+    #[serde(with = "serialization::bytes")]
+    pub output: Vec<u8>,
+    /// The state changes, if requested.
+    pub state_diff: Option<StateDiff>,
+    /// The action traces, if requested.
+    #[serde(default)]
+    pub trace: Vec<ActionTrace>,
+    /// The virtual machine execution trace, if requested.
+    pub vm_trace: Option<VmTrace>,
+    /// The hash of the transaction. Only included in the results of
+    /// `trace_replayBlockTransactions`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<Digest>,
+}
+
+impl Debug for TraceResults {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TraceResults")
+            .field("output", &hex::Hex(&self.output))
+            .field("state_diff", &self.state_diff)
+            .field("trace", &self.trace)
+            .field("vm_trace", &self.vm_trace)
+            .field("transaction_hash", &self.transaction_hash)
+            .finish()
+    }
+}
+
+/// The state changes of the accounts touched by a transaction.
+pub type StateDiff = HashMap<Address, AccountDiff>;
+
+/// The state changes of an account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountDiff {
+    /// The balance change.
+    pub balance: Delta<U256>,
+    /// The nonce change.
+    pub nonce: Delta<u64>,
+    /// The code change.
+    pub code: Delta<Vec<u8>>,
+    /// The storage slot changes.
+    pub storage: HashMap<Digest, Delta<Digest>>,
+}
+
+impl Serialize for AccountDiff {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Value<'a> {
+            balance: &'a Delta<U256>,
+            nonce: Delta<serialization::num::Quantity<u64>>,
+            code: Delta<String>,
+            storage: &'a HashMap<Digest, Delta<Digest>>,
+        }
+
+        Value {
+            balance: &self.balance,
+            nonce: self
+                .nonce
+                .as_ref()
+                .map(|nonce| serialization::num::Quantity(*nonce)),
+            code: self
+                .code
+                .as_ref()
+                .map(|code| serialization::bytes::encode(code)),
+            storage: &self.storage,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AccountDiff {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Value {
+            balance: Delta<U256>,
+            nonce: Delta<serialization::num::Quantity<u64>>,
+            code: Delta<String>,
+            storage: HashMap<Digest, Delta<Digest>>,
+        }
+
+        let value = Value::deserialize(deserializer)?;
+        Ok(Self {
+            balance: value.balance,
+            nonce: value.nonce.map(|nonce| nonce.0),
+            code: value
+                .code
+                .try_map(|code| serialization::bytes::decode::<D::Error>(&code))?,
+            storage: value.storage,
+        })
+    }
+}
+
+/// A change to a value in the state.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Delta<T> {
+    /// The value did not change.
+    #[serde(rename = "=")]
+    Unchanged,
+    /// The value was added.
+    #[serde(rename = "+")]
+    Added(T),
+    /// The value was removed.
+    #[serde(rename = "-")]
+    Removed(T),
+    /// The value was changed.
+    #[serde(rename = "*")]
+    Changed(Change<T>),
+}
+
+impl<T> Delta<T> {
+    fn as_ref(&self) -> Delta<&T> {
+        match self {
+            Self::Unchanged => Delta::Unchanged,
+            Self::Added(value) => Delta::Added(value),
+            Self::Removed(value) => Delta::Removed(value),
+            Self::Changed(Change { from, to }) => Delta::Changed(Change { from, to }),
+        }
+    }
+
+    fn map<U>(self, mut f: impl FnMut(T) -> U) -> Delta<U> {
+        let Ok(delta) = self.try_map(|value| Ok::<_, Infallible>(f(value)));
+        delta
+    }
+
+    fn try_map<U, E>(self, mut f: impl FnMut(T) -> Result<U, E>) -> Result<Delta<U>, E> {
+        Ok(match self {
+            Self::Unchanged => Delta::Unchanged,
+            Self::Added(value) => Delta::Added(f(value)?),
+            Self::Removed(value) => Delta::Removed(f(value)?),
+            Self::Changed(Change { from, to }) => Delta::Changed(Change {
+                from: f(from)?,
+                to: f(to)?,
+            }),
+        })
+    }
+}
+
+/// A changed value in the state.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Change<T> {
+    /// The value before the change.
+    pub from: T,
+    /// The value after the change.
+    pub to: T,
+}
+
+/// A virtual machine execution trace.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmTrace {
+    /// The code being executed.
+    #[serde(with = "serialization::bytes")]
+    pub code: Vec<u8>,
+    /// The executed instructions.
+    pub ops: Vec<VmInstruction>,
+}
+
+impl Debug for VmTrace {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("VmTrace")
+            .field("code", &hex::Hex(&self.code))
+            .field("ops", &self.ops)
+            .finish()
+    }
+}
+
+/// An executed virtual machine instruction.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmInstruction {
+    /// The program counter.
+    pub pc: u64,
+    /// The gas cost of the instruction.
+    pub cost: u64,
+    /// The result of executing the instruction, or [`None`] if it failed.
+    pub ex: Option<VmExecutedOperation>,
+    /// The trace of a sub-call or creation performed by the instruction.
+    pub sub: Option<VmTrace>,
+}
+
+/// The result of executing a virtual machine instruction.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmExecutedOperation {
+    /// The remaining gas.
+    pub used: u64,
+    /// The values pushed onto the stack.
+    pub push: Vec<U256>,
+    /// The memory write.
+    pub mem: Option<VmMemoryDiff>,
+    /// The storage write.
+    pub store: Option<VmStorageDiff>,
+}
+
+/// A memory write performed by a virtual machine instruction.
+#[derive(Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmMemoryDiff {
+    /// The memory offset.
+    pub off: u64,
+    /// The written data.
+    #[serde(with = "serialization::bytes")]
+    pub data: Vec<u8>,
+}
+
+impl Debug for VmMemoryDiff {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("VmMemoryDiff")
+            .field("off", &self.off)
+            .field("data", &hex::Hex(&self.data))
+            .finish()
+    }
+}
+
+/// A storage write performed by a virtual machine instruction.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmStorageDiff {
+    /// The storage slot.
+    pub key: U256,
+    /// The written value.
+    pub val: U256,
+}
+
+/// Transactions in the transaction pool, keyed by sender address and nonce.
+pub type PoolTransactions<T> = HashMap<Address, BTreeMap<u64, T>>;
+
+/// A signed transaction in the transaction pool.
+///
+/// Unlike transactions returned by `eth_getTransactionByHash`, pool
+/// transactions are not yet included in a block, so nodes report their block
+/// hash, block number and transaction index as `null`. These are set to zero
+/// in the wrapped [`SignedTransaction`], and are serialized as `null` again.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingTransaction {
+    /// The signed transaction.
+    pub transaction: SignedTransaction,
+}
+
+impl PendingTransaction {
+    /// Fields that are always `null` for pending transactions.
+    const BLOCK_FIELDS: [&str; 3] = ["blockHash", "blockNumber", "transactionIndex"];
+}
+
+impl Serialize for PendingTransaction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut value = serde_json::to_value(&self.transaction).map_err(ser::Error::custom)?;
+        for field in Self::BLOCK_FIELDS {
+            value[field] = serde_json::Value::Null;
+        }
+        value.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PendingTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut value = serde_json::Map::deserialize(deserializer)?;
+        let zeros = [
+            serde_json::to_value(Digest::default()).map_err(de::Error::custom)?,
+            "0x0".into(),
+            "0x0".into(),
+        ];
+        for (field, zero) in Self::BLOCK_FIELDS.into_iter().zip(zeros) {
+            match value.insert(field.to_owned(), zero) {
+                None | Some(serde_json::Value::Null) => {}
+                Some(_) => {
+                    return Err(de::Error::custom(format!(
+                        "pending transaction with non-null {field}"
+                    )));
+                }
+            }
+        }
+        let transaction = SignedTransaction::deserialize(serde_json::Value::Object(value))
+            .map_err(de::Error::custom)?;
+        Ok(Self { transaction })
+    }
+}
+
+/// The contents of the transaction pool.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TxpoolContent {
+    /// Transactions that are ready to be included in the next block.
+    pub pending: PoolTransactions<PendingTransaction>,
+    /// Transactions that are waiting on a nonce gap to be filled.
+    pub queued: PoolTransactions<PendingTransaction>,
+}
+
+/// The contents of the transaction pool for a single sender, keyed by nonce.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TxpoolContentFrom {
+    /// Transactions that are ready to be included in the next block.
+    pub pending: BTreeMap<u64, PendingTransaction>,
+    /// Transactions that are waiting on a nonce gap to be filled.
+    pub queued: BTreeMap<u64, PendingTransaction>,
+}
+
+/// The number of transactions in the transaction pool.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TxpoolStatus {
+    /// The number of pending transactions.
+    #[serde(with = "serialization::num")]
+    pub pending: u64,
+    /// The number of queued transactions.
+    #[serde(with = "serialization::num")]
+    pub queued: u64,
+}
+
+/// A textual summary of the contents of the transaction pool.
+///
+/// Each transaction is summarized as a string of the form
+/// `"{to}: {value} wei + {gas} gas × {gas price} wei"`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TxpoolInspect {
+    /// Summaries of pending transactions.
+    pub pending: PoolTransactions<String>,
+    /// Summaries of queued transactions.
+    pub queued: PoolTransactions<String>,
+}
+
+/// A parsed client version string, as returned by `web3_clientVersion`.
+///
+/// Client versions are conventionally formatted as
+/// `{name}[/{identity}]/{version}/{os}/{language}`, for example
+/// `Geth/v1.14.11-stable-f3c696fa/linux-amd64/go1.23.2`. Parsing is lenient, and
+/// components that are missing from the version string are [`None`].
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ClientVersion {
+    /// The client name, such as `Geth` or `reth`.
+    pub name: String,
+    /// The node identity, which some clients allow operators to configure.
+    pub identity: Option<String>,
+    /// The client version.
+    pub version: Option<String>,
+    /// The operating system and architecture.
+    pub os: Option<String>,
+    /// The programming language and its version.
+    pub language: Option<String>,
+}
+
+impl ClientVersion {
+    /// Returns the kind of client, for applying client-specific workarounds.
+    pub fn kind(&self) -> ClientKind {
+        let name = self.name.to_ascii_lowercase();
+        match name.as_str() {
+            "geth" => ClientKind::Geth,
+            "reth" => ClientKind::Reth,
+            "erigon" => ClientKind::Erigon,
+            "nethermind" => ClientKind::Nethermind,
+            "besu" => ClientKind::Besu,
+            "anvil" => ClientKind::Anvil,
+            _ if name.starts_with("hardhat") => ClientKind::Hardhat,
+            _ => ClientKind::Other,
+        }
+    }
+}
+
+impl FromStr for ClientVersion {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn is_version(component: &str) -> bool {
+            component
+                .strip_prefix('v')
+                .unwrap_or(component)
+                .starts_with(|c: char| c.is_ascii_digit())
+        }
+        fn join(components: &[&str]) -> Option<String> {
+            (!components.is_empty()).then(|| components.join("/"))
+        }
+
+        let components = s.split('/').collect::<Vec<_>>();
+        let (name, rest) = components.split_first().expect("split is never empty");
+        let Some(position) = rest.iter().position(|component| is_version(component)) else {
+            return Ok(Self {
+                name: name.to_string(),
+                identity: join(rest),
+                ..Default::default()
+            });
+        };
+
+        let (identity, rest) = rest.split_at(position);
+        Ok(Self {
+            name: name.to_string(),
+            identity: join(identity),
+            version: rest.first().map(|version| version.to_string()),
+            os: rest.get(1).map(|os| os.to_string()),
+            language: rest.get(2..).and_then(join),
+        })
+    }
+}
+
+impl Display for ClientVersion {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.name)?;
+        for component in [&self.identity, &self.version, &self.os, &self.language]
+            .into_iter()
+            .flatten()
+        {
+            write!(f, "/{component}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for ClientVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ClientVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Ok(version) = Cow::<str>::deserialize(deserializer)?.parse();
+        Ok(version)
+    }
+}
+
+/// A well-known execution client.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ClientKind {
+    /// Go Ethereum.
+    Geth,
+    /// Reth.
+    Reth,
+    /// Erigon.
+    Erigon,
+    /// Nethermind.
+    Nethermind,
+    /// Hyperledger Besu.
+    Besu,
+    /// Foundry's Anvil development node.
+    Anvil,
+    /// Hardhat Network development node.
+    Hardhat,
+    /// Any other client.
+    Other,
+}
+
+/// Configuration for resetting a development node with `anvil_reset` or
+/// `hardhat_reset`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetConfig {
+    /// The chain to fork from, or [`None`] to reset to a fresh local chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forking: Option<ForkConfig>,
+}
+
+/// The chain that a development node forks from.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkConfig {
+    /// The URL of the node to fork from. Defaults to the node that is
+    /// currently being forked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_rpc_url: Option<String>,
+    /// The block number to fork from. Defaults to the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+}
+
+/// Information about the running node, as returned by `admin_nodeInfo`.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    /// The hex-encoded node ID.
+    pub id: String,
+    /// The client version of the node.
+    pub name: ClientVersion,
+    /// The enode URL of the node.
+    pub enode: String,
+    /// The Ethereum node record of the node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enr: Option<String>,
+    /// The IP address of the node.
+    pub ip: String,
+    /// The ports the node is listening on.
+    pub ports: NodePorts,
+    /// The address the node is listening on for peer-to-peer connections.
+    pub listen_addr: String,
+    /// Information about the protocols supported by the node, keyed by
+    /// protocol name.
+    pub protocols: HashMap<String, NodeProtocol>,
+}
+
+/// The ports a node is listening on.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NodePorts {
+    /// The UDP port used for peer discovery.
+    pub discovery: u16,
+    /// The TCP port used for peer-to-peer connections.
+    pub listener: u16,
+}
+
+/// Information about a protocol supported by a node.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum NodeProtocol {
+    /// The `eth` protocol.
+    Eth(EthProtocolInfo),
+    /// Any other protocol.
+    Other(jsonrpc::Value),
+}
+
+/// Information about the `eth` protocol of a node.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthProtocolInfo {
+    /// The network ID.
+    pub network: u64,
+    /// The genesis block hash.
+    pub genesis: Digest,
+    /// The hash of the head block.
+    pub head: Digest,
+    /// Any additional protocol information reported by the node, such as the
+    /// chain configuration or total difficulty.
+    #[serde(flatten)]
+    pub extra: HashMap<String, jsonrpc::Value>,
+}
+
+/// Information about a connected peer, as returned by `admin_peers`.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerInfo {
+    /// The hex-encoded node ID of the peer.
+    pub id: String,
+    /// The client version of the peer.
+    pub name: ClientVersion,
+    /// The enode URL of the peer.
+    pub enode: String,
+    /// The Ethereum node record of the peer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enr: Option<String>,
+    /// The protocol capabilities advertised by the peer (for example
+    /// `eth/68`).
+    pub caps: Vec<String>,
+    /// Information about the network connection to the peer.
+    pub network: PeerNetworkInfo,
+    /// Information about the protocols negotiated with the peer, keyed by
+    /// protocol name.
+    pub protocols: HashMap<String, PeerProtocol>,
+}
+
+/// Information about the network connection to a peer.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerNetworkInfo {
+    /// The local address of the connection.
+    pub local_address: String,
+    /// The remote address of the connection.
+    pub remote_address: String,
+    /// Whether or not the connection was initiated by the peer.
+    pub inbound: bool,
+    /// Whether or not the peer is trusted.
+    pub trusted: bool,
+    /// Whether or not the peer is a static peer.
+    #[serde(rename = "static")]
+    pub static_: bool,
+}
+
+/// Information about a protocol negotiated with a peer.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum PeerProtocol {
+    /// The negotiated protocol version.
+    Version {
+        /// The protocol version.
+        version: u64,
+        /// Any additional protocol information reported by the node, such as
+        /// the peer's head block hash or total difficulty.
+        #[serde(flatten)]
+        extra: HashMap<String, jsonrpc::Value>,
+    },
+    /// Any other protocol information. Some nodes report the string
+    /// `"handshake"` while the protocol handshake is still in progress.
+    Other(jsonrpc::Value),
+}
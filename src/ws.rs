@@ -0,0 +1,215 @@
+//! Ethereum JSON RPC WebSocket client.
+//!
+//! Concurrent JSON RPC calls are multiplexed over a single WebSocket
+//! connection, with responses matched to their requests by JSON RPC ID. If the
+//! connection drops, it is re-established on the next call.
+
+use crate::{
+    duplex::{self, ClientError, Connection},
+    jsonrpc::{self, Id, JsonError, batch},
+    transport::{PubSubTransport, Transport},
+    types::SubscriptionKind,
+};
+use futures::{SinkExt, StreamExt as _};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use tokio::net::TcpStream;
+pub use tokio_tungstenite::tungstenite;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, tungstenite::Message};
+
+/// An Ethereum JSON RPC WebSocket client.
+///
+/// Calls are executed with the [`TransportExt`](crate::transport::TransportExt)
+/// extension methods, and subscriptions are created with the
+/// [`PubSubTransport`] methods.
+pub struct Client {
+    inner: duplex::Client<Socket>,
+}
+
+/// A subscription to node events over a WebSocket connection.
+pub type Subscription<T> = duplex::Subscription<T, Error>;
+
+impl Client {
+    /// Creates a new JSON RPC WebSocket client for the specified URL.
+    ///
+    /// The connection is established lazily on the first call. Note that this
+    /// spawns a background worker task that owns the connection, and must be
+    /// called from within a Tokio runtime.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            inner: duplex::Client::new(url.into()),
+        }
+    }
+}
+
+impl Transport for Client {
+    type Error = Error;
+
+    async fn roundtrip(&self, request: jsonrpc::Request) -> Result<jsonrpc::Response, Error> {
+        self.inner.roundtrip(request).await
+    }
+
+    async fn roundtrip_batch(
+        &self,
+        requests: Vec<jsonrpc::Request>,
+    ) -> Result<Vec<jsonrpc::Response>, Error> {
+        self.inner.roundtrip_batch(requests).await
+    }
+}
+
+impl PubSubTransport for Client {
+    async fn subscribe<T>(&self, kind: SubscriptionKind) -> Result<Subscription<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.inner.subscribe(kind).await
+    }
+}
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+impl Connection for Socket {
+    type Endpoint = String;
+    type Error = Error;
+
+    async fn connect(url: &String) -> Result<Self, Error> {
+        let (socket, _) = tokio_tungstenite::connect_async(url).await?;
+        Ok(socket)
+    }
+
+    async fn send(&mut self, message: String) -> Result<(), Error> {
+        SinkExt::send(self, Message::text(message)).await?;
+        Ok(())
+    }
+
+    async fn receive(&mut self) -> Option<Vec<u8>> {
+        loop {
+            match self.next().await? {
+                Ok(Message::Text(text)) => return Some(text.as_bytes().to_vec()),
+                Ok(Message::Binary(data)) => return Some(data.to_vec()),
+                Ok(Message::Close(_)) | Err(_) => return None,
+                Ok(_) => continue,
+            }
+        }
+    }
+
+    async fn close(mut self) {
+        let _ = SinkExt::close(&mut self).await;
+    }
+}
+
+/// An error using a WebSocket client.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON error: {0}")]
+    Json(#[from] JsonError),
+    #[error("WebSocket error: {0}")]
+    WebSocket(#[from] tungstenite::Error),
+    #[error("WebSocket connection closed")]
+    Closed,
+    #[error("request ID {} is already in use by another call", .0.0)]
+    DuplicateId(Id),
+    #[error(transparent)]
+    Rpc(#[from] jsonrpc::Error),
+    #[error(transparent)]
+    Batch(#[from] batch::Error),
+}
+
+impl ClientError for Error {
+    fn closed() -> Self {
+        Self::Closed
+    }
+
+    fn duplicate_id(id: Id) -> Self {
+        Self::DuplicateId(id)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eth, transport::TransportExt as _, types::Empty};
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    /// Starts a WebSocket node that answers every request with the number of
+    /// requests it received on the current connection, and closes connections
+    /// after `limit` requests.
+    async fn node(limit: u64) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let mut count = 0_u64;
+                while let Some(Ok(Message::Text(message))) = socket.next().await {
+                    count += 1;
+                    let reply = |request: &serde_json::Value| json!({ "jsonrpc": "2.0", "result": format!("{count:#x}"), "id": request["id"] });
+                    let response = match serde_json::from_str(&message).unwrap() {
+                        serde_json::Value::Array(requests) => {
+                            requests.iter().map(reply).collect::<serde_json::Value>()
+                        }
+                        request => reply(&request),
+                    };
+                    SinkExt::send(&mut socket, Message::text(response.to_string()))
+                        .await
+                        .unwrap();
+                    if count == limit {
+                        let _ = SinkExt::close(&mut socket).await;
+                        break;
+                    }
+                }
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn calls_over_websocket() {
+        let client = Client::new(node(u64::MAX).await);
+
+        assert_eq!(client.call_np(eth::BlockNumber).await.unwrap(), 1);
+        assert_eq!(
+            client
+                .batch(((eth::ChainId, Empty), (eth::BlockNumber, Empty)))
+                .await
+                .unwrap(),
+            (2, 2),
+        );
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_node_closes_connection() {
+        let client = Client::new(node(1).await);
+
+        assert_eq!(client.call_np(eth::BlockNumber).await.unwrap(), 1);
+
+        // The node closes the connection after every request, so the worker
+        // may not notice the connection was closed before the next call.
+        let mut result = client.call_np(eth::BlockNumber).await;
+        if matches!(result, Err(Error::Closed | Error::WebSocket(_))) {
+            result = client.call_np(eth::BlockNumber).await;
+        }
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn fails_to_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let client = Client::new(url);
+        assert!(matches!(
+            client.call_np(eth::BlockNumber).await,
+            Err(Error::WebSocket(_)),
+        ));
+    }
+}